@@ -1,10 +1,12 @@
 advent_of_code::solution!(5);
 
-use std::collections::HashMap;
-use std::marker::PhantomData;
+use std::fmt;
+use std::ops::Range;
 
 use itertools::Itertools;
 
+/// A move, with stacks as indexes into [`Stacks::columns`].
+#[derive(Clone, Debug, PartialEq, Eq)]
 struct Move {
     count: usize,
     from: usize,
@@ -12,104 +14,572 @@ struct Move {
 }
 
 impl Move {
-    fn new(s: &str) -> Self {
-        let chunks = s.split(' ').collect_vec();
-        let count = chunks[1].parse().unwrap();
-        let from = chunks[3].parse().unwrap();
-        let to = chunks[5].parse().unwrap();
-        Self { count, from, to }
+    fn parse(s: &str, stacks: &Stacks) -> Result<Self, MoveError> {
+        let invalid = || MoveError::InvalidMove(s.into());
+        let [count, from, to] = match s.split(' ').collect_vec()[..] {
+            ["move", count, "from", from, "to", to] => [count, from, to],
+            _ => return Err(invalid()),
+        };
+        let stack = |label: &str| {
+            stacks
+                .index(label)
+                .ok_or_else(|| MoveError::UnknownStack(label.into()))
+        };
+        Ok(Self {
+            count: count.parse().map_err(|_| invalid())?,
+            from: stack(from)?,
+            to: stack(to)?,
+        })
     }
 }
 
-trait Mover {}
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum MoveError {
+    InvalidMove(String),
+    UnknownStack(String),
+    NotEnoughCrates { count: usize, available: usize },
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoveError::InvalidMove(s) => write!(f, "`{s}` is not a move"),
+            MoveError::UnknownStack(label) => write!(f, "there is no stack `{label}`"),
+            MoveError::NotEnoughCrates { count, available } => {
+                write!(f, "cannot take {count} crates from a stack of {available}")
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Error {
+    MissingMoves,
+    Diagram(DiagramError),
+    /// An invalid or impossible move, numbered from 1.
+    Move {
+        number: usize,
+        error: MoveError,
+    },
+}
 
-struct CrateMover9000 {}
-impl Mover for CrateMover9000 {}
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::MissingMoves => write!(f, "expected a blank line between diagram and moves"),
+            Error::Diagram(error) => write!(f, "invalid diagram: {error}"),
+            Error::Move { number, error } => write!(f, "move {number}: {error}"),
+        }
+    }
+}
 
-struct CrateMover9001 {}
-impl Mover for CrateMover9001 {}
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum DiagramError {
+    MissingLabels,
+    /// A cell that is not a single glyph in brackets, such as `[AB]`.
+    InvalidCrate {
+        line: usize,
+        cell: String,
+    },
+    /// A crate that does not sit above exactly one stack label.
+    UnalignedCrate {
+        line: usize,
+        column: usize,
+    },
+    /// A crate with an empty space below it.
+    FloatingCrate {
+        line: usize,
+        stack: String,
+    },
+}
 
-struct Crane<M: Mover> {
+impl fmt::Display for DiagramError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiagramError::MissingLabels => write!(f, "the diagram has no stack labels"),
+            DiagramError::InvalidCrate { line, cell } => {
+                write!(f, "line {line}: `{cell}` is not a crate")
+            }
+            DiagramError::UnalignedCrate { line, column } => write!(
+                f,
+                "line {line}: the crate at column {column} is not above a single stack"
+            ),
+            DiagramError::FloatingCrate { line, stack } => {
+                write!(f, "line {line}: crate on stack {stack} is floating")
+            }
+        }
+    }
+}
+
+/// Splits a line into whitespace separated cells, with the range of characters each one covers.
+fn cells(line: &str) -> Vec<(Range<usize>, String)> {
+    let mut cells = Vec::new();
+    let mut current: Option<(usize, String)> = None;
+
+    for (i, c) in line.chars().chain([' ']).enumerate() {
+        match (c.is_whitespace(), current.as_mut()) {
+            (false, Some((_, cell))) => cell.push(c),
+            (false, None) => current = Some((i, c.to_string())),
+            (true, Some(_)) => {
+                let (start, cell) = current.take().unwrap();
+                cells.push((start..i, cell));
+            }
+            (true, None) => {}
+        }
+    }
+    cells
+}
+
+/// Crate stacks as drawn in the puzzle input. Stack labels can be any width, crates any glyph.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Stacks {
+    labels: Vec<String>,
+    /// Crates of each stack, bottom first.
     columns: Vec<Vec<char>>,
-    moves: Vec<Move>,
-    mover: PhantomData<M>,
 }
 
-impl<M: Mover> Crane<M> {
-    fn new(s: &str) -> Self {
-        let (picture, moves) = s.split_once("\n\n").unwrap();
-        let moves = moves.lines().map(Move::new).collect_vec();
-        let mut lines = picture.lines().rev();
-        let indexes = lines.next().unwrap();
-        let mut next = 0;
-        let indexes: HashMap<usize, usize> =
-            HashMap::from_iter(indexes.as_bytes().iter().enumerate().filter_map(|(i, b)| {
-                if b.is_ascii_digit() {
-                    let idx = next;
-                    next += 1;
-                    Some((i, idx))
-                } else {
-                    None
-                }
-            }));
-        let mut columns: Vec<Vec<char>> = Vec::from_iter(indexes.iter().map(|_| Vec::new()));
-        for l in lines {
-            for (i, b) in l.as_bytes().iter().enumerate() {
-                if b.is_ascii_uppercase() {
-                    let index = indexes.get(&i).unwrap();
-                    columns[*index].push(*b as char);
+impl Stacks {
+    fn parse(diagram: &str) -> Result<Self, DiagramError> {
+        let lines = diagram.lines().collect_vec();
+        let (label_line, crate_lines) = lines.split_last().ok_or(DiagramError::MissingLabels)?;
+
+        let (spans, labels): (Vec<_>, Vec<_>) = cells(label_line).into_iter().unzip();
+        if labels.is_empty() {
+            return Err(DiagramError::MissingLabels);
+        }
+        let mut columns = vec![Vec::new(); labels.len()];
+
+        for (row, (number, line)) in crate_lines.iter().enumerate().rev().enumerate() {
+            let number = number + 1;
+            for (span, cell) in cells(line) {
+                let glyph = match cell.chars().collect_vec()[..] {
+                    ['[', glyph, ']'] => glyph,
+                    _ => return Err(DiagramError::InvalidCrate { line: number, cell }),
+                };
+                let overlapping = spans
+                    .iter()
+                    .positions(|label| label.start < span.end && span.start < label.end)
+                    .collect_vec();
+                let [stack] = overlapping[..] else {
+                    return Err(DiagramError::UnalignedCrate {
+                        line: number,
+                        column: span.start + 1,
+                    });
+                };
+                if columns[stack].len() != row {
+                    return Err(DiagramError::FloatingCrate {
+                        line: number,
+                        stack: labels[stack].clone(),
+                    });
                 }
+                columns[stack].push(glyph);
             }
         }
-        let mover = PhantomData;
+
+        Ok(Self { labels, columns })
+    }
+
+    fn index(&self, label: &str) -> Option<usize> {
+        self.labels.iter().position(|l| l == label)
+    }
+
+    fn tops(&self) -> String {
+        String::from_iter(self.columns.iter().filter_map(|c| c.last()))
+    }
+}
+
+/// Draws the stacks in the format of the puzzle input, widening cells to fit the longest label.
+impl fmt::Display for Stacks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .labels
+            .iter()
+            .map(|l| l.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(3);
+        let height = self.columns.iter().map(Vec::len).max().unwrap_or(0);
+
+        for row in (0..height).rev() {
+            let cells = self.columns.iter().map(|column| match column.get(row) {
+                Some(glyph) => format!("{:^width$}", format!("[{glyph}]")),
+                None => " ".repeat(width),
+            });
+            writeln!(f, "{}", cells.format(" "))?;
+        }
+        let labels = self.labels.iter().map(|l| format!("{l:^width$}"));
+        write!(f, "{}", labels.format(" "))
+    }
+}
+
+/// How a crane carries out moves.
+trait Mover {
+    /// Checks that `apply` can carry out the move.
+    fn validate(&self, columns: &[Vec<char>], m: &Move) -> Result<(), MoveError> {
+        let available = columns[m.from].len();
+        if m.count > available {
+            return Err(MoveError::NotEnoughCrates {
+                count: m.count,
+                available,
+            });
+        }
+        Ok(())
+    }
+
+    /// Carries out a move that passed [`Mover::validate`].
+    fn apply(&mut self, columns: &mut [Vec<char>], m: &Move);
+
+    /// Number of times the crane lifts crates to carry out a move.
+    fn lifts(&self, m: &Move) -> usize;
+}
+
+/// Moves crates one at a time.
+struct CrateMover9000;
+
+impl Mover for CrateMover9000 {
+    fn apply(&mut self, columns: &mut [Vec<char>], m: &Move) {
+        for _ in 0..m.count {
+            let top = columns[m.from].pop().expect("validated move");
+            columns[m.to].push(top);
+        }
+    }
+
+    fn lifts(&self, m: &Move) -> usize {
+        m.count
+    }
+}
+
+/// Moves all crates of a move at once, keeping their order.
+struct CrateMover9001;
+
+impl Mover for CrateMover9001 {
+    fn apply(&mut self, columns: &mut [Vec<char>], m: &Move) {
+        let len = columns[m.from].len();
+        let top = columns[m.from].split_off(len - m.count);
+        columns[m.to].extend(top);
+    }
+
+    fn lifts(&self, m: &Move) -> usize {
+        m.count.min(1)
+    }
+}
+
+/// Moves at most `capacity` crates at once, keeping the order of the crates in each lift.
+/// A capacity of 1 behaves like [`CrateMover9000`], an unlimited one like [`CrateMover9001`].
+#[cfg(any(test, feature = "demo"))]
+struct CapacityLimited {
+    capacity: std::num::NonZeroUsize,
+}
+
+#[cfg(any(test, feature = "demo"))]
+impl Mover for CapacityLimited {
+    fn apply(&mut self, columns: &mut [Vec<char>], m: &Move) {
+        let mut left = m.count;
+        while left > 0 {
+            let count = left.min(self.capacity.get());
+            CrateMover9001.apply(columns, &Move { count, ..*m });
+            left -= count;
+        }
+    }
+
+    fn lifts(&self, m: &Move) -> usize {
+        m.count.div_ceil(self.capacity.get())
+    }
+}
+
+/// Wraps a mover and adds up the cost of its moves: every lift costs the number of stacks travelled.
+#[cfg(any(test, feature = "demo"))]
+struct Metered<M: Mover> {
+    mover: M,
+    costs: Vec<usize>,
+}
+
+#[cfg(any(test, feature = "demo"))]
+impl<M: Mover> Metered<M> {
+    fn new(mover: M) -> Self {
         Self {
-            columns,
-            moves,
             mover,
+            costs: Vec::new(),
         }
     }
 
-    fn column_string(&self) -> String {
-        String::from_iter(self.columns.iter().filter_map(|c| c.last()))
+    fn total(&self) -> usize {
+        self.costs.iter().sum()
     }
 }
 
-impl Crane<CrateMover9000> {
-    fn moves(&mut self) {
-        for m in &self.moves {
-            for _ in 0..m.count {
-                let top = self.columns[m.from - 1].pop().unwrap();
-                self.columns[m.to - 1].push(top);
-            }
-        }
+#[cfg(any(test, feature = "demo"))]
+impl<M: Mover> Mover for Metered<M> {
+    fn validate(&self, columns: &[Vec<char>], m: &Move) -> Result<(), MoveError> {
+        self.mover.validate(columns, m)
     }
+
+    fn apply(&mut self, columns: &mut [Vec<char>], m: &Move) {
+        self.costs.push(self.lifts(m) * m.from.abs_diff(m.to));
+        self.mover.apply(columns, m);
+    }
+
+    fn lifts(&self, m: &Move) -> usize {
+        self.mover.lifts(m)
+    }
+}
+
+struct Crane {
+    stacks: Stacks,
+    moves: Vec<Move>,
 }
 
-impl Crane<CrateMover9001> {
-    fn moves(&mut self) {
-        for m in &self.moves {
-            let len = self.columns[m.from - 1].len();
-            let top = self.columns[m.from - 1].split_off(len - m.count);
-            self.columns[m.to - 1].extend(top);
+impl Crane {
+    fn new(s: &str) -> Self {
+        Self::parse(s).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    fn parse(s: &str) -> Result<Self, Error> {
+        let (diagram, moves) = s.split_once("\n\n").ok_or(Error::MissingMoves)?;
+        let stacks = Stacks::parse(diagram).map_err(Error::Diagram)?;
+        let moves = moves
+            .lines()
+            .enumerate()
+            .map(|(i, m)| {
+                Move::parse(m, &stacks).map_err(|error| Error::Move {
+                    number: i + 1,
+                    error,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { stacks, moves })
+    }
+
+    /// Carries out all moves with the given mover and returns how many lifts that took.
+    /// Stops at the first impossible move.
+    fn run(&mut self, mover: &mut impl Mover) -> Result<usize, Error> {
+        #[cfg(feature = "demo")]
+        self.show(None);
+        let mut lifts = 0;
+        for (i, m) in self.moves.iter().enumerate() {
+            mover
+                .validate(&self.stacks.columns, m)
+                .map_err(|error| Error::Move {
+                    number: i + 1,
+                    error,
+                })?;
+            lifts += mover.lifts(m);
+            mover.apply(&mut self.stacks.columns, m);
+            #[cfg(feature = "demo")]
+            self.show(Some(m));
         }
+        Ok(lifts)
+    }
+
+    #[cfg(feature = "demo")]
+    fn show(&self, m: Option<&Move>) {
+        if let Some(m) = m {
+            let labels = &self.stacks.labels;
+            println!(
+                "move {} from {} to {}",
+                m.count, labels[m.from], labels[m.to]
+            );
+        }
+        println!("{}\n", self.stacks);
+    }
+}
+
+/// Returns the crates on top of each stack after all moves, or `None` if a move is impossible.
+fn top_crates(input: &str, mover: &mut impl Mover) -> Option<String> {
+    let mut crane = Crane::new(input);
+    if let Err(e) = crane.run(mover) {
+        eprintln!("{e}");
+        return None;
     }
+    Some(crane.stacks.tops())
 }
 
 pub fn part_one(input: &str) -> Option<String> {
-    let mut crane = Crane::<CrateMover9000>::new(input);
-    crane.moves();
-    Some(crane.column_string())
+    #[cfg(feature = "demo")]
+    {
+        let mut mover = Metered::new(CrateMover9000);
+        let tops = top_crates(input, &mut mover);
+        println!("Cost: {}", mover.total());
+        tops
+    }
+    #[cfg(not(feature = "demo"))]
+    top_crates(input, &mut CrateMover9000)
 }
 
 pub fn part_two(input: &str) -> Option<String> {
-    let mut crane = Crane::<CrateMover9001>::new(input);
-    crane.moves();
-    Some(crane.column_string())
+    #[cfg(feature = "demo")]
+    {
+        let mut mover = Metered::new(CrateMover9001);
+        let tops = top_crates(input, &mut mover);
+        println!("Cost: {}", mover.total());
+
+        let capacity = std::num::NonZeroUsize::new(2).expect("a non-zero capacity");
+        let mut limited = Metered::new(CapacityLimited { capacity });
+        if top_crates(input, &mut limited).is_some() {
+            println!("Cost with a capacity of {capacity}: {}", limited.total());
+        }
+        tops
+    }
+    #[cfg(not(feature = "demo"))]
+    top_crates(input, &mut CrateMover9001)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rstest::rstest;
+
+    const EXAMPLE_DIAGRAM: &str = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 ";
+
+    #[test]
+    fn test_parse_diagram() {
+        let stacks = Stacks::parse(EXAMPLE_DIAGRAM).unwrap();
+        assert_eq!(stacks.labels, ["1", "2", "3"]);
+        assert_eq!(
+            stacks.columns,
+            [vec!['Z', 'N'], vec!['M', 'C', 'D'], vec!['P']]
+        );
+        assert_eq!(stacks.tops(), "NDP");
+    }
+
+    #[rstest]
+    #[case(EXAMPLE_DIAGRAM, EXAMPLE_DIAGRAM)]
+    #[case(
+        "[a]\n[1] [b] [c] [d] [e] [f] [g] [h] [i] [j] [ä]\n 1   2   3   4   5   6   7   8   9   10  11",
+        "[a]                                        \n[1] [b] [c] [d] [e] [f] [g] [h] [i] [j] [ä]\n 1   2   3   4   5   6   7   8   9  10  11 "
+    )]
+    #[case(
+        "     [★]\n[x]  [y]\n top  mid  bot ",
+        "    [★]    \n[x] [y]    \ntop mid bot"
+    )]
+    #[case("[x]\n 10000 ", " [x] \n10000")]
+    #[case("\n 1   2 ", " 1   2 ")]
+    fn test_render(#[case] diagram: &str, #[case] expected: &str) {
+        let stacks = Stacks::parse(diagram).unwrap();
+        assert_eq!(stacks.to_string(), expected);
+        assert_eq!(Stacks::parse(expected), Ok(stacks));
+    }
+
+    #[test]
+    fn test_wide_labels() {
+        let stacks = Stacks::parse("[x]  [y]\n top  mid  bot ").unwrap();
+        assert_eq!(stacks.columns, [vec!['x'], vec!['y'], vec![]]);
+        assert_eq!(stacks.index("bot"), Some(2));
+    }
+
+    #[rstest]
+    #[case("", DiagramError::MissingLabels)]
+    #[case("[A]\n   ", DiagramError::MissingLabels)]
+    #[case("[AB]\n 1 ", DiagramError::InvalidCrate { line: 1, cell: "[AB]".into() })]
+    #[case("A\n 1 ", DiagramError::InvalidCrate { line: 1, cell: "A".into() })]
+    #[case("      [A]\n 1   2 ", DiagramError::UnalignedCrate { line: 1, column: 7 })]
+    #[case("[A]\n    \n 1 ", DiagramError::FloatingCrate { line: 1, stack: "1".into() })]
+    fn test_invalid_diagrams(#[case] diagram: &str, #[case] expected: DiagramError) {
+        assert_eq!(Stacks::parse(diagram), Err(expected));
+    }
+
+    #[test]
+    fn test_many_stacks() {
+        let input = [
+            "[A]                                     [K]",
+            "[B] [C] [D] [E] [F] [G] [H] [I] [J] [L] [M]",
+            " 1   2   3   4   5   6   7   8   9  10  11 ",
+            "",
+            "move 1 from 10 to 2",
+            "move 2 from 11 to 1",
+        ]
+        .join("\n");
+        assert_eq!(part_one(&input), Some("MLDEFGHIJ".to_string()));
+        assert_eq!(part_two(&input), Some("KLDEFGHIJ".to_string()));
+    }
+
+    fn example() -> Crane {
+        Crane::new(&advent_of_code::template::read_file("examples", DAY))
+    }
+
+    fn capacity(capacity: usize) -> CapacityLimited {
+        CapacityLimited {
+            capacity: capacity.try_into().unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_lifts() {
+        assert_eq!(example().run(&mut CrateMover9000), Ok(7));
+        assert_eq!(example().run(&mut CrateMover9001), Ok(4));
+        assert_eq!(example().run(&mut capacity(2)), Ok(5));
+    }
+
+    #[test]
+    fn test_capacity_limited() {
+        let mut crane = example();
+        crane.run(&mut capacity(1)).unwrap();
+        assert_eq!(crane.stacks.tops(), "CMZ");
+
+        let mut crane = example();
+        crane.run(&mut capacity(3)).unwrap();
+        assert_eq!(crane.stacks.tops(), "MCD");
+
+        let mut columns = vec![vec!['A', 'B', 'C', 'D', 'E'], vec![]];
+        let m = Move {
+            count: 5,
+            from: 0,
+            to: 1,
+        };
+        capacity(2).apply(&mut columns, &m);
+        assert_eq!(columns[1], ['D', 'E', 'B', 'C', 'A']);
+    }
+
+    #[test]
+    fn test_metered() {
+        let mut mover = Metered::new(CrateMover9000);
+        example().run(&mut mover).unwrap();
+        assert_eq!(mover.costs, [1, 6, 2, 1]);
+        assert_eq!(mover.total(), 10);
+
+        let mut mover = Metered::new(CrateMover9001);
+        example().run(&mut mover).unwrap();
+        assert_eq!(mover.total(), 5);
+    }
+
+    #[rstest]
+    #[case("move 4 from 1 to 2", MoveError::NotEnoughCrates { count: 4, available: 2 })]
+    #[case("move 1 from 3 to 2\nmove 2 from 3 to 1", MoveError::NotEnoughCrates { count: 2, available: 0 })]
+    fn test_impossible_moves(#[case] moves: &str, #[case] expected: MoveError) {
+        let input = format!("{EXAMPLE_DIAGRAM}\n\n{moves}");
+        let mut crane = Crane::parse(&input).unwrap();
+        let number = moves.lines().count();
+        assert_eq!(
+            crane.run(&mut CrateMover9001),
+            Err(Error::Move {
+                number,
+                error: expected
+            })
+        );
+        assert_eq!(part_one(&input), None);
+        assert_eq!(part_two(&input), None);
+    }
+
+    #[rstest]
+    #[case(EXAMPLE_DIAGRAM, Error::MissingMoves)]
+    #[case("\n\nmove 1 from 1 to 1", Error::Diagram(DiagramError::MissingLabels))]
+    #[case("{EXAMPLE}move 1 from 4 to 1", Error::Move { number: 1, error: MoveError::UnknownStack("4".into()) })]
+    #[case("{EXAMPLE}move 1 from 1 to 2\nmove one from 1 to 2", Error::Move { number: 2, error: MoveError::InvalidMove("move one from 1 to 2".into()) })]
+    #[case("{EXAMPLE}move 1 to 2", Error::Move { number: 1, error: MoveError::InvalidMove("move 1 to 2".into()) })]
+    fn test_invalid_input(#[case] input: &str, #[case] expected: Error) {
+        let input = input.replace("{EXAMPLE}", &format!("{EXAMPLE_DIAGRAM}\n\n"));
+        assert_eq!(Crane::parse(&input).err(), Some(expected));
+    }
+
+    #[test]
+    fn test_error_message() {
+        let mut crane = Crane::parse(&format!("{EXAMPLE_DIAGRAM}\n\nmove 4 from 1 to 2")).unwrap();
+        let error = crane.run(&mut CrateMover9000).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "move 1: cannot take 4 crates from a stack of 2"
+        );
+    }
 
     #[test]
     fn test_part_one() {
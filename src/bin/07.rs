@@ -1,119 +1,739 @@
-advent_of_code::solution!(7);
+advent_of_code::solution!(7, parse = Filesystem::new);
 
-use itertools::Itertools;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
+use std::fmt;
 
-type Path = String;
+type InodeId = usize;
+
+const ROOT: InodeId = 0;
+
+/// A single line of `ls` output: `dir name` or `size name`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Dirent {
+    Dir,
+    File(usize),
+}
+
+impl fmt::Display for Dirent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Dirent::Dir => write!(f, "dir"),
+            Dirent::File(size) => write!(f, "{size}"),
+        }
+    }
+}
 
 #[derive(Clone, Debug)]
-struct Dirent {
-    path: Path,
-    node: INode,
+enum Kind {
+    /// Entries by name. `listed` is set once `ls` has shown the contents of the directory.
+    Dir {
+        entries: BTreeMap<String, InodeId>,
+        listed: bool,
+    },
+    File,
 }
 
 #[derive(Clone, Debug)]
-enum INode {
-    Dir,
-    File(usize),
+struct Inode {
+    name: String,
+    parent: Option<InodeId>,
+    kind: Kind,
+    /// Size of the file, or the total size of the subtree for a directory.
+    size: usize,
 }
 
-struct Filesystem {
-    directories: HashMap<Path, Vec<Dirent>>,
+impl Inode {
+    fn dirent(&self) -> Dirent {
+        match self.kind {
+            Kind::Dir { .. } => Dirent::Dir,
+            Kind::File => Dirent::File(self.size),
+        }
+    }
+
+    fn entries(&self) -> Option<&BTreeMap<String, InodeId>> {
+        match &self.kind {
+            Kind::Dir { entries, .. } => Some(entries),
+            Kind::File => None,
+        }
+    }
 }
 
-fn form_path(path: &[&str]) -> Path {
-    format!("/{}", path.join("/"))
+/// Reasons a transcript cannot have come from a real terminal session.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    InvalidLine(String),
+    UnknownCommand(String),
+    /// A line of output that does not follow an `ls`.
+    OutputWithoutLs,
+    /// `cd` into a directory that no `ls` has shown.
+    UnknownDirectory {
+        cwd: String,
+        name: String,
+    },
+    NotADirectory(String),
+    NoSuchPath(String),
+    DuplicateEntry(String),
+    /// Two listings of the same directory disagree. `None` means the entry is absent from that listing.
+    ConflictingEntry {
+        path: String,
+        before: Option<Dirent>,
+        after: Option<Dirent>,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidLine(line) => write!(f, "invalid line `{line}`"),
+            Error::UnknownCommand(command) => write!(f, "unknown command `{command}`"),
+            Error::OutputWithoutLs => write!(f, "output without a preceding `ls`"),
+            Error::UnknownDirectory { cwd, name } => {
+                write!(f, "no directory `{name}` has been listed in `{cwd}`")
+            }
+            Error::NotADirectory(path) => write!(f, "`{path}` is not a directory"),
+            Error::NoSuchPath(path) => write!(f, "`{path}` does not exist"),
+            Error::DuplicateEntry(path) => write!(f, "`{path}` is listed twice"),
+            Error::ConflictingEntry {
+                path,
+                before,
+                after,
+            } => match (before, after) {
+                (Some(before), Some(after)) => {
+                    write!(f, "`{path}` was listed as `{before}`, later as `{after}`")
+                }
+                (None, _) => write!(f, "`{path}` is missing from an earlier listing"),
+                (_, None) => write!(f, "`{path}` is missing from a later listing"),
+            },
+        }
+    }
+}
+
+impl Error {
+    fn at(self, line: usize) -> TranscriptError {
+        TranscriptError { line, error: self }
+    }
+}
+
+/// An [`Error`] together with the (1-based) number of the transcript line that caused it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TranscriptError {
+    line: usize,
+    error: Error,
+}
+
+impl fmt::Display for TranscriptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.error)
+    }
+}
+
+/// A line of a transcript: a command, or one line of `ls` output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Line<'a> {
+    Cd(&'a str),
+    Ls,
+    Output(&'a str, Dirent),
+}
+
+impl<'a> Line<'a> {
+    fn parse(line: &'a str) -> Result<Self, Error> {
+        if let Some(command) = line.strip_prefix("$ ") {
+            return match command.split_once(' ') {
+                Some(("cd", arg)) if !arg.is_empty() => Ok(Line::Cd(arg)),
+                None if command == "ls" => Ok(Line::Ls),
+                _ => Err(Error::UnknownCommand(command.into())),
+            };
+        }
+
+        let invalid = || Error::InvalidLine(line.into());
+        let (size_type, name) = line.split_once(' ').ok_or_else(invalid)?;
+        let dirent = if size_type == "dir" {
+            Dirent::Dir
+        } else {
+            Dirent::File(size_type.parse().map_err(|_| invalid())?)
+        };
+        Ok(Line::Output(name, dirent))
+    }
+}
+
+impl fmt::Display for Line<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Line::Cd(arg) => write!(f, "$ cd {arg}"),
+            Line::Ls => write!(f, "$ ls"),
+            Line::Output(name, dirent) => write!(f, "{dirent} {name}"),
+        }
+    }
+}
+
+/// State of an `ls` whose output is being read.
+struct Listing {
+    dir: InodeId,
+    /// Whether the directory has been listed before, so entries must match the earlier listing.
+    relisting: bool,
+    seen: Vec<String>,
+}
+
+/// Replays a transcript one line at a time, building up the filesystem it explores.
+struct Shell {
+    fs: Filesystem,
+    cwd: InodeId,
+    listing: Option<Listing>,
+}
+
+impl Shell {
+    fn new() -> Self {
+        Shell {
+            fs: Filesystem::empty(),
+            cwd: ROOT,
+            listing: None,
+        }
+    }
+
+    fn run(&mut self, line: Line) -> Result<(), Error> {
+        match line {
+            Line::Cd(arg) => {
+                self.finish_listing()?;
+                self.cwd = self.fs.cd(self.cwd, arg)?;
+            }
+            Line::Ls => {
+                self.finish_listing()?;
+                self.listing = Some(self.fs.start_listing(self.cwd));
+            }
+            Line::Output(name, dirent) => {
+                let listing = self.listing.as_mut().ok_or(Error::OutputWithoutLs)?;
+                self.fs.record(listing, name, dirent)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn finish_listing(&mut self) -> Result<(), Error> {
+        match self.listing.take() {
+            Some(listing) => self.fs.finish_listing(listing),
+            None => Ok(()),
+        }
+    }
+}
+
+pub struct Filesystem {
+    inodes: Vec<Inode>,
 }
 
 impl Filesystem {
     fn new(input: &str) -> Self {
-        let mut directories: HashMap<Path, Vec<Dirent>> = HashMap::new();
-        let mut path = Vec::new();
-        for line in input.lines() {
-            if let Some(arg) = line.strip_prefix("$ cd ") {
-                match arg {
-                    ".." => {
-                        path.pop();
-                    }
-                    "/" => path.clear(),
-                    _ => path.push(arg),
+        Self::parse(input).unwrap_or_else(|e| panic!("invalid transcript: {e}"))
+    }
+
+    fn parse(input: &str) -> Result<Self, TranscriptError> {
+        let mut shell = Shell::new();
+        let mut last = 0;
+
+        for (i, line) in input.lines().enumerate() {
+            last = i + 1;
+            Line::parse(line)
+                .and_then(|line| shell.run(line))
+                .map_err(|e| e.at(last))?;
+        }
+
+        // a listing at the end of the transcript is checked against the last line.
+        shell.finish_listing().map_err(|e| e.at(last))?;
+        Ok(shell.fs)
+    }
+
+    fn empty() -> Self {
+        Filesystem {
+            inodes: vec![Inode {
+                name: String::new(),
+                parent: None,
+                kind: Kind::Dir {
+                    entries: BTreeMap::new(),
+                    listed: false,
+                },
+                size: 0,
+            }],
+        }
+    }
+
+    fn path(&self, id: InodeId) -> String {
+        let mut names = Vec::new();
+        let mut current = Some(id);
+        while let Some(id) = current {
+            names.push(self.inodes[id].name.as_str());
+            current = self.inodes[id].parent;
+        }
+        names.pop();
+        names.reverse();
+        format!("/{}", names.join("/"))
+    }
+
+    fn child_path(&self, dir: InodeId, name: &str) -> String {
+        match dir {
+            ROOT => format!("/{name}"),
+            _ => format!("{}/{name}", self.path(dir)),
+        }
+    }
+
+    fn cd(&self, cwd: InodeId, arg: &str) -> Result<InodeId, Error> {
+        match arg {
+            "/" => Ok(ROOT),
+            // like a real shell, `cd ..` in the root stays there.
+            ".." => Ok(self.inodes[cwd].parent.unwrap_or(ROOT)),
+            name => {
+                let unknown = || Error::UnknownDirectory {
+                    cwd: self.path(cwd),
+                    name: name.into(),
+                };
+                let &id = self.inodes[cwd]
+                    .entries()
+                    .and_then(|entries| entries.get(name))
+                    .ok_or_else(unknown)?;
+                match self.inodes[id].kind {
+                    Kind::Dir { .. } => Ok(id),
+                    Kind::File => Err(Error::NotADirectory(self.path(id))),
                 }
-                continue;
             }
-            if line.starts_with('$') {
-                continue;
+        }
+    }
+
+    fn start_listing(&mut self, dir: InodeId) -> Listing {
+        let Kind::Dir { listed, .. } = &mut self.inodes[dir].kind else {
+            unreachable!("only directories can be the working directory");
+        };
+        let relisting = std::mem::replace(listed, true);
+        Listing {
+            dir,
+            relisting,
+            seen: Vec::new(),
+        }
+    }
+
+    fn record(&mut self, listing: &mut Listing, name: &str, dirent: Dirent) -> Result<(), Error> {
+        if listing.seen.iter().any(|seen| seen == name) {
+            return Err(Error::DuplicateEntry(self.child_path(listing.dir, name)));
+        }
+        listing.seen.push(name.into());
+
+        let existing = self.inodes[listing.dir]
+            .entries()
+            .and_then(|entries| entries.get(name))
+            .map(|&id| self.inodes[id].dirent());
+
+        match existing {
+            Some(before) if before == dirent => Ok(()),
+            None if !listing.relisting => {
+                self.insert(listing.dir, name, dirent);
+                Ok(())
             }
+            before => Err(Error::ConflictingEntry {
+                path: self.child_path(listing.dir, name),
+                before,
+                after: Some(dirent),
+            }),
+        }
+    }
 
-            let full_path = form_path(&path);
+    fn finish_listing(&self, listing: Listing) -> Result<(), Error> {
+        let entries = self.inodes[listing.dir].entries().into_iter().flatten();
+        for (name, &id) in entries {
+            if !listing.seen.contains(name) {
+                return Err(Error::ConflictingEntry {
+                    path: self.path(id),
+                    before: Some(self.inodes[id].dirent()),
+                    after: None,
+                });
+            }
+        }
+        Ok(())
+    }
 
-            let (size_type, name) = line.split_once(' ').unwrap();
-            let node = if size_type == "dir" {
-                INode::Dir
-            } else {
-                INode::File(size_type.parse().unwrap())
-            };
+    /// Adds an entry to a directory, updating the sizes of all its ancestors.
+    fn insert(&mut self, dir: InodeId, name: &str, dirent: Dirent) -> InodeId {
+        let id = self.inodes.len();
+        let (kind, size) = match dirent {
+            Dirent::Dir => (
+                Kind::Dir {
+                    entries: BTreeMap::new(),
+                    listed: false,
+                },
+                0,
+            ),
+            Dirent::File(size) => (Kind::File, size),
+        };
+        self.inodes.push(Inode {
+            name: name.into(),
+            parent: Some(dir),
+            kind,
+            size,
+        });
+        if let Kind::Dir { entries, .. } = &mut self.inodes[dir].kind {
+            entries.insert(name.into(), id);
+        }
 
-            path.push(name);
-            let file = Dirent {
-                path: form_path(&path),
-                node,
-            };
-            path.pop();
+        let mut current = Some(dir);
+        while let Some(ancestor) = current {
+            self.inodes[ancestor].size += size;
+            current = self.inodes[ancestor].parent;
+        }
+        id
+    }
 
-            directories.entry(full_path.clone()).or_default();
+    fn directory_sizes(&self) -> impl Iterator<Item = usize> + '_ {
+        self.inodes
+            .iter()
+            .filter(|inode| matches!(inode.kind, Kind::Dir { .. }))
+            .map(|inode| inode.size)
+    }
+}
 
-            directories.entry(full_path).and_modify(|e| e.push(file));
+#[cfg(any(test, feature = "demo"))]
+impl Filesystem {
+    fn lookup(&self, path: &str) -> Result<InodeId, Error> {
+        let mut id = ROOT;
+        for name in path.split('/').filter(|name| !name.is_empty()) {
+            let entries = self.inodes[id]
+                .entries()
+                .ok_or_else(|| Error::NotADirectory(self.path(id)))?;
+            id = *entries
+                .get(name)
+                .ok_or_else(|| Error::NoSuchPath(path.into()))?;
         }
-        Self { directories }
+        Ok(id)
     }
 
-    fn directory_size(&self, p: &Path) -> usize {
-        let mut result = 0;
-        for entry in self.directories.get(p).unwrap().iter() {
-            result += match entry.node {
-                INode::Dir => self.directory_size(&entry.path),
-                INode::File(size) => size,
+    /// Like `du -d max_depth path`: sizes of the directories below `path`, children before their parents.
+    fn du(&self, path: &str, max_depth: usize) -> Result<Vec<(String, usize)>, Error> {
+        fn visit(
+            fs: &Filesystem,
+            id: InodeId,
+            depth: usize,
+            max_depth: usize,
+            out: &mut Vec<(String, usize)>,
+        ) {
+            if depth < max_depth {
+                for &child in fs.inodes[id]
+                    .entries()
+                    .into_iter()
+                    .flatten()
+                    .map(|(_, c)| c)
+                {
+                    if fs.inodes[child].entries().is_some() {
+                        visit(fs, child, depth + 1, max_depth, out);
+                    }
+                }
             }
+            out.push((fs.path(id), fs.inodes[id].size));
         }
-        result
+
+        let id = self.lookup(path)?;
+        let mut out = Vec::new();
+        visit(self, id, 0, max_depth, &mut out);
+        Ok(out)
     }
 
-    fn directory_sizes(&self) -> Vec<usize> {
-        self.directories
-            .keys()
-            .map(|p| self.directory_size(p))
-            .collect()
+    /// Renders the subtree at `path` like `tree`, with the size of every entry.
+    fn tree(&self, path: &str, max_depth: Option<usize>) -> Result<String, Error> {
+        fn label(inode: &Inode) -> String {
+            match inode.kind {
+                Kind::Dir { .. } => format!("{}/ ({})", inode.name, inode.size),
+                Kind::File => format!("{} ({})", inode.name, inode.size),
+            }
+        }
+
+        fn visit(
+            fs: &Filesystem,
+            id: InodeId,
+            prefix: &str,
+            depth: usize,
+            max_depth: Option<usize>,
+            out: &mut String,
+        ) {
+            if max_depth.is_some_and(|max| depth >= max) {
+                return;
+            }
+            let Some(entries) = fs.inodes[id].entries() else {
+                return;
+            };
+            for (i, &child) in entries.values().enumerate() {
+                let last = i + 1 == entries.len();
+                let (branch, indent) = if last {
+                    ("└── ", "    ")
+                } else {
+                    ("├── ", "│   ")
+                };
+                out.push_str(&format!("{prefix}{branch}{}\n", label(&fs.inodes[child])));
+                visit(
+                    fs,
+                    child,
+                    &format!("{prefix}{indent}"),
+                    depth + 1,
+                    max_depth,
+                    out,
+                );
+            }
+        }
+
+        let id = self.lookup(path)?;
+        let mut out = match id {
+            ROOT => format!("/ ({})\n", self.inodes[ROOT].size),
+            _ => format!("{}\n", label(&self.inodes[id])),
+        };
+        visit(self, id, "", 0, max_depth, &mut out);
+        Ok(out)
     }
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let fs = Filesystem::new(input);
-    Some(fs.directory_sizes().iter().filter(|&s| *s <= 100000).sum())
+#[cfg(test)]
+impl Filesystem {
+    fn size(&self, path: &str) -> Result<usize, Error> {
+        Ok(self.inodes[self.lookup(path)?].size)
+    }
+
+    /// Emits a transcript that explores this filesystem depth first, listing directories in name order.
+    /// Parsing it gives back the same filesystem.
+    fn transcript(&self) -> String {
+        let mut lines = vec![Line::Cd("/")];
+        self.emit(ROOT, &mut lines);
+        // walking back up at the end does not show anything.
+        while lines.last() == Some(&Line::Cd("..")) {
+            lines.pop();
+        }
+        lines
+            .iter()
+            .map(Line::to_string)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn emit<'a>(&'a self, dir: InodeId, lines: &mut Vec<Line<'a>>) {
+        let Kind::Dir {
+            entries,
+            listed: true,
+        } = &self.inodes[dir].kind
+        else {
+            return;
+        };
+
+        lines.push(Line::Ls);
+        for (name, &id) in entries {
+            lines.push(Line::Output(name, self.inodes[id].dirent()));
+        }
+        for (name, &id) in entries {
+            // directories that were never listed would only add an empty `cd`.
+            if let Kind::Dir { listed: true, .. } = self.inodes[id].kind {
+                lines.push(Line::Cd(name));
+                self.emit(id, lines);
+                lines.push(Line::Cd(".."));
+            }
+        }
+    }
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let fs = Filesystem::new(input);
-    let unused = 70000000 - fs.directory_size(&String::from("/"));
+pub fn part_one(fs: &Filesystem) -> Option<usize> {
+    #[cfg(feature = "demo")]
+    print!("{}", fs.tree("/", None).unwrap());
+    Some(fs.directory_sizes().filter(|&s| s <= 100000).sum())
+}
+
+pub fn part_two(fs: &Filesystem) -> Option<usize> {
+    #[cfg(feature = "demo")]
+    for (path, size) in fs.du("/", 1).unwrap() {
+        println!("{size}\t{path}");
+    }
+    let unused = 70000000usize.checked_sub(fs.inodes[ROOT].size)?;
     fs.directory_sizes()
-        .iter()
-        .sorted()
-        .find(|&s| unused + s > 30000000)
-        .copied()
+        .filter(|&s| unused + s > 30000000)
+        .min()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::{generators::day07, property::check};
+    use rstest::rstest;
+
+    fn example() -> Filesystem {
+        Filesystem::new(&advent_of_code::template::read_file("examples", DAY))
+    }
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&example());
         assert_eq!(result, Some(95437));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&example());
         assert_eq!(result, Some(24933642));
     }
+
+    #[rstest]
+    #[case("/", 48381165)]
+    #[case("/a", 94853)]
+    #[case("/a/e", 584)]
+    #[case("/a/e/", 584)]
+    #[case("/d", 24933642)]
+    #[case("/b.txt", 14848514)]
+    fn test_size(#[case] path: &str, #[case] expected: usize) {
+        assert_eq!(example().size(path), Ok(expected));
+    }
+
+    #[test]
+    fn test_lookup_errors() {
+        let fs = example();
+        assert_eq!(fs.size("/x"), Err(Error::NoSuchPath("/x".into())));
+        assert_eq!(
+            fs.size("/b.txt/x"),
+            Err(Error::NotADirectory("/b.txt".into()))
+        );
+    }
+
+    #[test]
+    fn test_du() {
+        let fs = example();
+        assert_eq!(
+            fs.du("/", 1).unwrap(),
+            [
+                ("/a".into(), 94853),
+                ("/d".into(), 24933642),
+                ("/".into(), 48381165)
+            ]
+        );
+        assert_eq!(
+            fs.du("/a", 5).unwrap(),
+            [("/a/e".into(), 584), ("/a".into(), 94853)]
+        );
+        assert_eq!(fs.du("/", 0).unwrap(), [("/".into(), 48381165)]);
+    }
+
+    #[test]
+    fn test_tree() {
+        let expected = [
+            "/ (48381165)",
+            "├── a/ (94853)",
+            "│   ├── e/ (584)",
+            "│   │   └── i (584)",
+            "│   ├── f (29116)",
+            "│   ├── g (2557)",
+            "│   └── h.lst (62596)",
+            "├── b.txt (14848514)",
+            "├── c.dat (8504156)",
+            "└── d/ (24933642)",
+            "    ├── d.ext (5626152)",
+            "    ├── d.log (8033020)",
+            "    ├── j (4060174)",
+            "    └── k (7214296)",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(example().tree("/", None).unwrap(), expected);
+        assert_eq!(
+            example().tree("/a", Some(1)).unwrap(),
+            "a/ (94853)\n├── e/ (584)\n├── f (29116)\n├── g (2557)\n└── h.lst (62596)\n"
+        );
+    }
+
+    #[test]
+    fn test_relisting_matches() {
+        let fs =
+            Filesystem::parse("$ ls\ndir a\n1 b\n$ cd a\n$ ls\n2 c\n$ cd ..\n$ ls\n1 b\ndir a")
+                .unwrap();
+        assert_eq!(fs.size("/"), Ok(3));
+    }
+
+    #[rstest]
+    #[case("$ cd a", Error::UnknownDirectory { cwd: "/".into(), name: "a".into() })]
+    #[case("$ ls\n1 a\n$ cd a", Error::NotADirectory("/a".into()))]
+    #[case("$ ls\nnonsense", Error::InvalidLine("nonsense".into()))]
+    #[case("$ rm -rf /", Error::UnknownCommand("rm -rf /".into()))]
+    #[case("$ cd", Error::UnknownCommand("cd".into()))]
+    #[case("1 a", Error::OutputWithoutLs)]
+    #[case("$ ls\ndir a\n$ cd a\n1 b", Error::OutputWithoutLs)]
+    #[case("$ ls\nx a", Error::InvalidLine("x a".into()))]
+    #[case("$ ls\n1 a\n2 a", Error::DuplicateEntry("/a".into()))]
+    #[case(
+        "$ ls\n1 a\n$ ls\n2 a",
+        Error::ConflictingEntry { path: "/a".into(), before: Some(Dirent::File(1)), after: Some(Dirent::File(2)) }
+    )]
+    #[case(
+        "$ ls\n1 a\n$ ls\ndir a",
+        Error::ConflictingEntry { path: "/a".into(), before: Some(Dirent::File(1)), after: Some(Dirent::Dir) }
+    )]
+    #[case(
+        "$ ls\n1 a\n$ ls\n1 a\n2 b",
+        Error::ConflictingEntry { path: "/b".into(), before: None, after: Some(Dirent::File(2)) }
+    )]
+    #[case(
+        "$ ls\n1 a\n2 b\n$ ls\n1 a",
+        Error::ConflictingEntry { path: "/b".into(), before: Some(Dirent::File(2)), after: None }
+    )]
+    fn test_invalid_transcripts(#[case] input: &str, #[case] expected: Error) {
+        assert_eq!(
+            Filesystem::parse(input).map_err(|e| e.error).err(),
+            Some(expected)
+        );
+    }
+
+    #[test]
+    fn test_error_lines() {
+        let error = Filesystem::parse("$ cd /\n$ ls\ndir a\n$ cd b")
+            .err()
+            .unwrap();
+        assert_eq!(error.line, 4);
+        assert_eq!(
+            error.to_string(),
+            "line 4: no directory `b` has been listed in `/`"
+        );
+
+        // a conflicting listing is only complete once the next command runs, or the transcript ends.
+        let input = "$ ls\n1 a\n2 b\n$ ls\n1 a";
+        assert_eq!(Filesystem::parse(input).err().unwrap().line, 5);
+        let input = "$ ls\n1 a\n2 b\n$ ls\n1 a\n$ cd /";
+        assert_eq!(Filesystem::parse(input).err().unwrap().line, 6);
+    }
+
+    #[test]
+    fn test_transcript() {
+        let expected = [
+            "$ cd /",
+            "$ ls",
+            "dir a",
+            "14848514 b.txt",
+            "8504156 c.dat",
+            "dir d",
+            "$ cd a",
+            "$ ls",
+            "dir e",
+            "29116 f",
+            "2557 g",
+            "62596 h.lst",
+            "$ cd e",
+            "$ ls",
+            "584 i",
+            "$ cd ..",
+            "$ cd ..",
+            "$ cd d",
+            "$ ls",
+            "5626152 d.ext",
+            "8033020 d.log",
+            "4060174 j",
+            "7214296 k",
+        ]
+        .join("\n");
+        assert_eq!(example().transcript(), expected);
+        assert_eq!(Filesystem::empty().transcript(), "$ cd /");
+    }
+
+    #[test]
+    fn test_transcript_roundtrip() {
+        check(64, |rng| {
+            let fs = Filesystem::new(&day07::input(rng, 12));
+            let transcript = fs.transcript();
+            let replayed = Filesystem::new(&transcript);
+            assert_eq!(replayed.transcript(), transcript);
+            assert_eq!(replayed.tree("/", None), fs.tree("/", None));
+            assert_eq!(part_one(&replayed), part_one(&fs));
+        });
+    }
 }
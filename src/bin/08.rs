@@ -70,7 +70,7 @@ impl std::ops::AddAssign for Point {
 }
 
 impl Point {
-    fn get<'a, T>(&'a self, f: &'a Array2<T>) -> Option<&T> {
+    fn get<'a, T>(&'a self, f: &'a Array2<T>) -> Option<&'a T> {
         if self.0 < 0 || self.1 < 0 {
             // Will be out of bounds if either index is < 0
             None
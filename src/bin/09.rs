@@ -44,7 +44,7 @@ struct Rope {
 impl Rope {
     fn new(length: usize) -> Self {
         Self {
-            segments: Vec::from_iter(std::iter::repeat(Point::default()).take(length + 1)),
+            segments: Vec::from_iter(std::iter::repeat_n(Point::default(), length + 1)),
             tails: HashSet::from_iter([Point::default()]),
         }
     }
@@ -1,79 +1,211 @@
-advent_of_code::solution!(10);
-
-use itertools::Itertools;
-
-pub fn part_one(input: &str) -> Option<i32> {
-    let program = input
-        .lines()
-        .flat_map(|l| {
-            if let Some(value) = l.strip_prefix("addx ") {
-                let value = value.parse::<i32>().expect("to be a number");
-                vec![0, value]
-            } else {
-                vec![0]
-            }
-        })
-        .collect_vec();
-
-    let mut x = 1;
-    let mut total = 0;
-    for (i, v) in (1..=220).zip(program) {
-        if (i - 20) % 40 == 0 {
-            total += i * x;
+advent_of_code::solution!(10, parse = Program::new);
+
+use advent_of_code::framebuffer::Framebuffer;
+
+const WIDTH: usize = 40;
+const HEIGHT: usize = 6;
+
+/// The signal strength is sampled every 40 cycles from cycle 20 on, up to this cycle.
+const LAST_SAMPLE: usize = 220;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Instruction {
+    Noop,
+    Addx(i32),
+}
+
+impl Instruction {
+    fn new(line: &str) -> Self {
+        match line.split_once(' ') {
+            None if line == "noop" => Instruction::Noop,
+            Some(("addx", value)) => Instruction::Addx(value.parse().expect("to be a number")),
+            _ => panic!("unknown instruction `{line}`"),
+        }
+    }
+
+    /// Number of cycles the instruction takes to complete.
+    fn cycles(self) -> usize {
+        match self {
+            Instruction::Noop => 1,
+            Instruction::Addx(_) => 2,
+        }
+    }
+
+    /// Updates the registers once the instruction completes.
+    fn apply(self, registers: &mut Registers) {
+        match self {
+            Instruction::Noop => {}
+            Instruction::Addx(value) => registers.x += value,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Registers {
+    x: i32,
+}
+
+impl Default for Registers {
+    fn default() -> Self {
+        Self { x: 1 }
+    }
+}
+
+/// Watches a running [`Cpu`].
+trait Observer {
+    /// Called during every cycle, numbered from 1, with the registers as they are during that cycle.
+    fn cycle(&mut self, cycle: usize, registers: &Registers);
+}
+
+impl<F: FnMut(usize, &Registers)> Observer for F {
+    fn cycle(&mut self, cycle: usize, registers: &Registers) {
+        self(cycle, registers)
+    }
+}
+
+/// Lets several observers watch the same run.
+impl<A: Observer, B: Observer> Observer for (A, B) {
+    fn cycle(&mut self, cycle: usize, registers: &Registers) {
+        self.0.cycle(cycle, registers);
+        self.1.cycle(cycle, registers);
+    }
+}
+
+#[derive(Debug, Default)]
+struct Cpu {
+    registers: Registers,
+    /// Number of completed cycles.
+    cycle: usize,
+}
+
+impl Cpu {
+    fn execute(&mut self, instruction: Instruction, observer: &mut impl Observer) {
+        for _ in 0..instruction.cycles() {
+            self.cycle += 1;
+            observer.cycle(self.cycle, &self.registers);
+        }
+        instruction.apply(&mut self.registers);
+    }
+
+    fn run(&mut self, program: &Program, observer: &mut impl Observer) {
+        for &instruction in &program.instructions {
+            self.execute(instruction, observer);
         }
-        x += v;
-    }
-
-    Some(total)
-}
-
-pub fn part_two(input: &str) -> Option<String> {
-    let program = input
-        .lines()
-        .flat_map(|l| {
-            if let Some(value) = l.strip_prefix("addx ") {
-                let value = value.parse::<i32>().expect("to be a number");
-                vec![0, value]
-            } else {
-                vec![0]
-            }
-        })
-        .collect_vec();
-
-    let mut x: i32 = 1;
-    let mut crt = [false; 240];
-    for (i, v) in (1..=240).zip(program) {
-        let pixel = ((i - 1) % 40) + 1;
-        let sprite = ((x - 1) % 40) + 1;
-        if pixel >= sprite && pixel <= sprite + 2 {
-            crt[(i - 1) as usize] = true;
+    }
+}
+
+pub struct Program {
+    instructions: Vec<Instruction>,
+}
+
+impl Program {
+    fn new(input: &str) -> Self {
+        Self {
+            instructions: input.lines().map(Instruction::new).collect(),
         }
-        x += v;
     }
+}
+
+/// Sums the signal strength, `cycle * x`, during the sampled cycles.
+#[derive(Debug, Default)]
+struct SignalStrength {
+    total: i32,
+}
 
-    let display = crt
-        .iter()
-        .map(|&x| if x { b'#' } else { b' ' })
-        .collect_vec()
-        .chunks(40)
-        .map(|scanline| String::from_utf8_lossy(scanline).into_owned())
-        .join("\n");
-    Some(display)
+impl Observer for SignalStrength {
+    fn cycle(&mut self, cycle: usize, registers: &Registers) {
+        if cycle <= LAST_SAMPLE && cycle % 40 == 20 {
+            self.total += cycle as i32 * registers.x;
+        }
+    }
+}
+
+/// Draws one pixel per cycle, lit when the three pixel wide sprite centered on `x` covers it.
+struct Crt {
+    screen: Framebuffer,
+}
+
+impl Default for Crt {
+    fn default() -> Self {
+        Self {
+            screen: Framebuffer::new(WIDTH, HEIGHT),
+        }
+    }
+}
+
+impl Observer for Crt {
+    fn cycle(&mut self, cycle: usize, registers: &Registers) {
+        let column = ((cycle - 1) % WIDTH) as i32;
+        // the beam stops once it has drawn the last row.
+        self.screen
+            .set_index(cycle - 1, column.abs_diff(registers.x) <= 1);
+    }
+}
+
+pub fn part_one(program: &Program) -> Option<i32> {
+    let mut signal = SignalStrength::default();
+    Cpu::default().run(program, &mut signal);
+    Some(signal.total)
+}
+
+pub fn part_two(program: &Program) -> Option<String> {
+    let mut crt = Crt::default();
+    Cpu::default().run(program, &mut crt);
+    #[cfg(feature = "demo")]
+    println!("{}", crt.screen.to_half_blocks());
+    Some(crt.screen.to_text('#', ' '))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn example() -> Program {
+        Program::new(&advent_of_code::template::read_file("examples", DAY))
+    }
+
+    #[test]
+    fn test_small_program() {
+        let program = Program::new("noop\naddx 3\naddx -5");
+        assert_eq!(
+            program.instructions,
+            [
+                Instruction::Noop,
+                Instruction::Addx(3),
+                Instruction::Addx(-5)
+            ]
+        );
+
+        let mut xs = Vec::new();
+        let mut cpu = Cpu::default();
+        cpu.run(&program, &mut |cycle, registers: &Registers| {
+            xs.push((cycle, registers.x))
+        });
+        assert_eq!(xs, [(1, 1), (2, 1), (3, 1), (4, 4), (5, 4)]);
+        assert_eq!(cpu.cycle, 5);
+        assert_eq!(cpu.registers, Registers { x: -1 });
+    }
+
+    #[test]
+    fn test_observers_share_a_run() {
+        let mut observers = (SignalStrength::default(), Crt::default());
+        Cpu::default().run(&example(), &mut observers);
+        assert_eq!(Some(observers.0.total), part_one(&example()));
+        assert_eq!(
+            Some(observers.1.screen.to_text('#', ' ')),
+            part_two(&example())
+        );
+    }
+
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&example());
         assert_eq!(result, Some(13140));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&example());
         let picture = &advent_of_code::template::read_file_part("examples", DAY, 2);
         assert_eq!(result, Some(picture.replace('.', " ").to_string()));
     }
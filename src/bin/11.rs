@@ -1,106 +1,378 @@
 use itertools::Itertools;
+use num_bigint::BigUint;
 use std::collections::VecDeque;
+use std::fmt;
+use winnow::prelude::*;
+use winnow::{
+    ascii::{dec_uint, line_ending, multispace0, space0},
+    combinator::{alt, cut_err, delimited, eof, preceded, separated, terminated},
+    error::{ContextError, StrContext, StrContextValue},
+};
 
 advent_of_code::solution!(11);
 
-type Value = u64;
+/// A worry level. `u64` is fast, [`BigUint`] is exact however large levels grow.
+trait Worry: Clone + fmt::Display {
+    fn new(value: u64) -> Self;
+    fn plus(&self, other: &Self) -> Self;
+    /// Panics if the level would drop below zero.
+    fn minus(&self, other: &Self) -> Self;
+    fn times(&self, other: &Self) -> Self;
+    fn divided_by(&self, n: u64) -> Self;
+    fn modulo(&self, n: u64) -> Self;
+    fn is_divisible_by(&self, n: u64) -> bool;
+}
+
+const OVERFLOW: &str = "worry level overflows a u64, use big integers";
+const UNDERFLOW: &str = "worry level drops below zero";
+
+impl Worry for u64 {
+    fn new(value: u64) -> Self {
+        value
+    }
+
+    fn plus(&self, other: &Self) -> Self {
+        self.checked_add(*other).expect(OVERFLOW)
+    }
+
+    fn minus(&self, other: &Self) -> Self {
+        self.checked_sub(*other).expect(UNDERFLOW)
+    }
+
+    fn times(&self, other: &Self) -> Self {
+        self.checked_mul(*other).expect(OVERFLOW)
+    }
 
-#[derive(Debug, Default)]
-enum Operation {
-    #[default]
-    Square,
-    Add(Value),
-    Mul(Value),
+    fn divided_by(&self, n: u64) -> Self {
+        self / n
+    }
+
+    fn modulo(&self, n: u64) -> Self {
+        self % n
+    }
+
+    // `u64::is_multiple_of` needs Rust 1.87.
+    #[allow(unknown_lints, clippy::manual_is_multiple_of)]
+    fn is_divisible_by(&self, n: u64) -> bool {
+        self % n == 0
+    }
 }
 
-impl Operation {
-    fn apply(&self, old: Value) -> Value {
-        use Operation::*;
+impl Worry for BigUint {
+    fn new(value: u64) -> Self {
+        BigUint::from(value)
+    }
+
+    fn plus(&self, other: &Self) -> Self {
+        self + other
+    }
+
+    fn minus(&self, other: &Self) -> Self {
+        assert!(self >= other, "{UNDERFLOW}");
+        self - other
+    }
+
+    fn times(&self, other: &Self) -> Self {
+        self * other
+    }
+
+    fn divided_by(&self, n: u64) -> Self {
+        self / n
+    }
+
+    fn modulo(&self, n: u64) -> Self {
+        self % n
+    }
+
+    fn is_divisible_by(&self, n: u64) -> bool {
+        (self % n) == BigUint::ZERO
+    }
+}
+
+/// How worry levels drop after a monkey inspects an item.
+trait Relief {
+    fn relieve<W: Worry>(&self, worry: W) -> W;
+}
+
+/// Divides levels by `n`, rounding down, like the relief in part 1.
+struct DivideBy(u64);
+
+impl Relief for DivideBy {
+    fn relieve<W: Worry>(&self, worry: W) -> W {
+        worry.divided_by(self.0)
+    }
+}
+
+/// Keeps levels below the least common multiple of all divisibility tests, which changes no test result.
+struct ModuloLcm(u64);
+
+impl ModuloLcm {
+    fn of<W: Worry>(puzzle: &Puzzle<W>) -> Self {
+        fn gcd(a: u64, b: u64) -> u64 {
+            if b == 0 {
+                a
+            } else {
+                gcd(b, a % b)
+            }
+        }
+        let lcm = puzzle
+            .monkeys
+            .iter()
+            .fold(1, |lcm, m| lcm / gcd(lcm, m.test) * m.test);
+        Self(lcm)
+    }
+}
+
+impl Relief for ModuloLcm {
+    fn relieve<W: Worry>(&self, worry: W) -> W {
+        worry.modulo(self.0)
+    }
+}
+
+/// Leaves levels as they are. Only [`BigUint`] levels survive more than a few rounds of this.
+#[cfg(any(test, feature = "demo"))]
+struct NoRelief;
+
+#[cfg(any(test, feature = "demo"))]
+impl Relief for NoRelief {
+    fn relieve<W: Worry>(&self, worry: W) -> W {
+        worry
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Operand {
+    Old,
+    Const(u64),
+}
+
+impl Operand {
+    fn value<W: Worry>(self, old: &W) -> W {
         match self {
-            Add(value) => old + value,
-            Mul(value) => old * value,
-            Square => old * old,
+            Operand::Old => old.clone(),
+            Operand::Const(value) => W::new(value),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Operator {
+    Add,
+    Sub,
+    Mul,
+}
+
+/// The right hand side of `new = old * 19`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Operation {
+    left: Operand,
+    operator: Operator,
+    right: Operand,
+}
+
+impl Operation {
+    fn apply<W: Worry>(&self, old: &W) -> W {
+        let (left, right) = (self.left.value(old), self.right.value(old));
+        match self.operator {
+            Operator::Add => left.plus(&right),
+            Operator::Sub => left.minus(&right),
+            Operator::Mul => left.times(&right),
         }
     }
 }
 
-#[derive(Debug, Default)]
-struct Monkey {
-    items: VecDeque<Value>,
+#[derive(Debug)]
+struct Monkey<W> {
+    items: VecDeque<W>,
     operation: Operation,
-    test: Value,
+    test: u64,
     matches: usize,
     otherwise: usize,
     inspected: usize,
 }
 
-impl Monkey {
-    fn new(s: &str) -> Self {
-        let mut monkey = Self::default();
-
-        for line in s.lines() {
-            let line = line.trim_start();
-            if let Some(items) = line.strip_prefix("Starting items: ") {
-                monkey
-                    .items
-                    .extend(items.split(", ").map(|i| i.parse::<Value>().unwrap()));
-            }
+fn operand(input: &mut &str) -> PResult<Operand> {
+    alt(("old".value(Operand::Old), dec_uint.map(Operand::Const)))
+        .context(StrContext::Expected(StrContextValue::Description(
+            "`old` or a number",
+        )))
+        .parse_next(input)
+}
 
-            if let Some(operation) = line.strip_prefix("Operation: new = old") {
-                if let Some(constant) = operation.strip_prefix(" + ") {
-                    monkey.operation = Operation::Add(constant.parse().unwrap());
-                }
-                if let Some(constant) = operation.strip_prefix(" * ") {
-                    if constant == "old" {
-                        monkey.operation = Operation::Square;
-                    } else {
-                        monkey.operation = Operation::Mul(constant.parse().unwrap());
-                    }
-                }
-            }
+fn operator(input: &mut &str) -> PResult<Operator> {
+    alt((
+        '+'.value(Operator::Add),
+        '-'.value(Operator::Sub),
+        '*'.value(Operator::Mul),
+    ))
+    .context(StrContext::Expected(StrContextValue::Description(
+        "`+`, `-` or `*`",
+    )))
+    .parse_next(input)
+}
 
-            if let Some(test) = line.strip_prefix("Test: divisible by ") {
-                monkey.test = test.parse().unwrap();
-            }
+fn operation(input: &mut &str) -> PResult<Operation> {
+    (operand, delimited(' ', operator, ' '), operand)
+        .map(|(left, operator, right)| Operation {
+            left,
+            operator,
+            right,
+        })
+        .parse_next(input)
+}
 
-            if let Some(id) = line.strip_prefix("If true: throw to monkey ") {
-                monkey.matches = id.parse().unwrap();
-            }
+/// A `label: value` line of the notes, indented by any amount. Once a monkey's header is parsed, all
+/// its fields are required, so errors point at the field that is missing or malformed.
+fn field<'a, O>(
+    label: &'static str,
+    value: impl Parser<&'a str, O, ContextError>,
+) -> impl Parser<&'a str, O, ContextError> {
+    cut_err(delimited(
+        (space0, label, ':', space0),
+        value,
+        alt((line_ending, eof)),
+    ))
+    .context(StrContext::Label(label))
+}
+
+/// Parses the notes of one monkey, together with its number.
+fn monkey<W: Worry>(input: &mut &str) -> PResult<(usize, Monkey<W>)> {
+    let id = delimited("Monkey ", dec_uint, (':', line_ending))
+        .context(StrContext::Label("monkey"))
+        .parse_next(input)?;
+    let items: Vec<u64> = field(
+        "Starting items",
+        separated(0.., dec_uint::<_, u64, _>, ", "),
+    )
+    .parse_next(input)?;
+    let operation = field("Operation", preceded("new = ", operation)).parse_next(input)?;
+    let test = field("Test", preceded("divisible by ", dec_uint)).parse_next(input)?;
+    let matches = field("If true", preceded("throw to monkey ", dec_uint)).parse_next(input)?;
+    let otherwise = field("If false", preceded("throw to monkey ", dec_uint)).parse_next(input)?;
+
+    let monkey = Monkey {
+        items: items.into_iter().map(W::new).collect(),
+        operation,
+        test,
+        matches,
+        otherwise,
+        inspected: 0,
+    };
+    Ok((id, monkey))
+}
+
+fn notes<W: Worry>(input: &mut &str) -> PResult<Vec<(usize, Monkey<W>)>> {
+    terminated(separated(1.., monkey, line_ending), multispace0).parse_next(input)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Error {
+    /// The notes do not follow the grammar, with a description of where and why.
+    Syntax(String),
+    /// Monkeys have to be numbered from 0, in order.
+    UnexpectedMonkey {
+        expected: usize,
+        found: usize,
+    },
+    ZeroDivisor {
+        monkey: usize,
+    },
+    TargetOutOfRange {
+        monkey: usize,
+        target: usize,
+    },
+}
 
-            if let Some(id) = line.strip_prefix("If false: throw to monkey ") {
-                monkey.otherwise = id.parse().unwrap();
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Syntax(error) => write!(f, "{error}"),
+            Error::UnexpectedMonkey { expected, found } => {
+                write!(
+                    f,
+                    "expected notes for monkey {expected}, found monkey {found}"
+                )
+            }
+            Error::ZeroDivisor { monkey } => {
+                write!(f, "monkey {monkey} tests for divisibility by 0")
             }
+            Error::TargetOutOfRange { monkey, target } => write!(
+                f,
+                "monkey {monkey} throws to monkey {target}, which does not exist"
+            ),
         }
-        monkey
+    }
+}
+
+/// State after a round, in the format of the puzzle description.
+#[cfg(any(test, feature = "demo"))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct RoundTrace {
+    round: usize,
+    /// Items each monkey inspected during the round.
+    inspected: Vec<usize>,
+    /// Worry levels of the items each monkey holds after the round.
+    items: Vec<Vec<String>>,
+}
+
+#[cfg(any(test, feature = "demo"))]
+impl fmt::Display for RoundTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "== After round {} ==", self.round)?;
+        for (id, items) in self.items.iter().enumerate() {
+            writeln!(f, "Monkey {id}: {}", items.join(", "))?;
+        }
+        for (id, inspected) in self.inspected.iter().enumerate() {
+            writeln!(f, "Monkey {id} inspected items {inspected} times.")?;
+        }
+        Ok(())
     }
 }
 
 #[derive(Debug)]
-struct Puzzle {
-    monkeys: Vec<Monkey>,
-    limit: Value,
+struct Puzzle<W> {
+    monkeys: Vec<Monkey<W>>,
 }
 
-impl Puzzle {
+impl<W: Worry> Puzzle<W> {
     fn new(s: &str) -> Self {
-        let monkeys = s.split("\n\n").map(Monkey::new).collect_vec();
-        let limit = monkeys.iter().map(|m| m.test).product();
-        Self { monkeys, limit }
+        Self::parse(s).unwrap_or_else(|e| panic!("invalid notes: {e}"))
     }
 
-    fn step(&mut self, calming: bool) {
+    fn parse(s: &str) -> Result<Self, Error> {
+        let notes = notes.parse(s).map_err(|e| Error::Syntax(e.to_string()))?;
+        let count = notes.len();
+
+        let mut monkeys = Vec::with_capacity(count);
+        for (expected, (found, monkey)) in notes.into_iter().enumerate() {
+            if found != expected {
+                return Err(Error::UnexpectedMonkey { expected, found });
+            }
+            if monkey.test == 0 {
+                return Err(Error::ZeroDivisor { monkey: found });
+            }
+            if let Some(&target) = [monkey.matches, monkey.otherwise]
+                .iter()
+                .find(|&&target| target >= count)
+            {
+                return Err(Error::TargetOutOfRange {
+                    monkey: found,
+                    target,
+                });
+            }
+            monkeys.push(monkey);
+        }
+        Ok(Self { monkeys })
+    }
+
+    fn step(&mut self, relief: &impl Relief) {
         for i in 0..self.monkeys.len() {
             let monkey = &mut self.monkeys[i];
             let mut throw = VecDeque::new();
             while let Some(value) = monkey.items.pop_front() {
                 monkey.inspected += 1;
-                let value = monkey.operation.apply(value);
-                let value = if calming {
-                    value / 3
-                } else {
-                    value % self.limit
-                };
-                let target = if value % monkey.test == 0 {
+                let value = relief.relieve(monkey.operation.apply(&value));
+                let target = if value.is_divisible_by(monkey.test) {
                     monkey.matches
                 } else {
                     monkey.otherwise
@@ -115,6 +387,37 @@ impl Puzzle {
         }
     }
 
+    fn run(&mut self, rounds: usize, relief: &impl Relief) {
+        for _ in 0..rounds {
+            self.step(relief);
+        }
+    }
+
+    /// Runs like [`Puzzle::run`], recording the state after every round.
+    #[cfg(any(test, feature = "demo"))]
+    fn trace(&mut self, rounds: usize, relief: &impl Relief) -> Vec<RoundTrace> {
+        (1..=rounds)
+            .map(|round| {
+                let before = self.monkeys.iter().map(|m| m.inspected).collect_vec();
+                self.step(relief);
+                RoundTrace {
+                    round,
+                    inspected: self
+                        .monkeys
+                        .iter()
+                        .zip(before)
+                        .map(|(m, before)| m.inspected - before)
+                        .collect(),
+                    items: self
+                        .monkeys
+                        .iter()
+                        .map(|m| m.items.iter().map(W::to_string).collect())
+                        .collect(),
+                }
+            })
+            .collect()
+    }
+
     fn monkey_business(&self) -> usize {
         self.monkeys
             .iter()
@@ -127,24 +430,194 @@ impl Puzzle {
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
-    let mut puzzle = Puzzle::new(input);
-    for _ in 0..20 {
-        puzzle.step(true);
+    let mut puzzle = Puzzle::<u64>::new(input);
+    #[cfg(feature = "demo")]
+    for trace in puzzle.trace(20, &DivideBy(3)) {
+        println!("{trace}");
+    }
+    #[cfg(not(feature = "demo"))]
+    puzzle.run(20, &DivideBy(3));
+    #[cfg(feature = "demo")]
+    {
+        let mut exact = Puzzle::<BigUint>::new(input);
+        exact.run(20, &NoRelief);
+        println!("Without relief: {}", exact.monkey_business());
     }
     Some(puzzle.monkey_business())
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let mut puzzle = Puzzle::new(input);
-    for _ in 0..10_000 {
-        puzzle.step(false);
-    }
+    let mut puzzle = Puzzle::<u64>::new(input);
+    let relief = ModuloLcm::of(&puzzle);
+    puzzle.run(10_000, &relief);
     Some(puzzle.monkey_business())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::{generators::day11, property::check};
+    use rstest::rstest;
+
+    fn example<W: Worry>() -> Puzzle<W> {
+        Puzzle::new(&advent_of_code::template::read_file("examples", DAY))
+    }
+
+    fn inspected<W>(puzzle: &Puzzle<W>) -> Vec<usize> {
+        puzzle.monkeys.iter().map(|m| m.inspected).collect()
+    }
+
+    #[test]
+    fn test_trace() {
+        let traces = example::<u64>().trace(2, &DivideBy(3));
+        assert_eq!(traces[0].inspected, [2, 4, 3, 5]);
+        assert_eq!(
+            traces[1].to_string(),
+            [
+                "== After round 2 ==",
+                "Monkey 0: 695, 10, 71, 135, 350",
+                "Monkey 1: 43, 49, 58, 55, 362",
+                "Monkey 2: ",
+                "Monkey 3: ",
+                "Monkey 0 inspected items 4 times.",
+                "Monkey 1 inspected items 6 times.",
+                "Monkey 2 inspected items 1 times.",
+                "Monkey 3 inspected items 5 times.",
+                ""
+            ]
+            .join("\n")
+        );
+    }
+
+    const NOTES: &str = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 4
+    If true: throw to monkey 1
+    If false: throw to monkey 1
+
+Monkey 1:
+  Starting items:
+  Operation: new = old + old
+  Test: divisible by 6
+    If true: throw to monkey 0
+    If false: throw to monkey 0";
+
+    #[test]
+    fn test_lcm() {
+        assert_eq!(ModuloLcm::of(&example::<u64>()).0, 23 * 19 * 13 * 17);
+        assert_eq!(ModuloLcm::of(&Puzzle::<u64>::new(NOTES)).0, 12);
+    }
+
+    #[rstest]
+    #[case("old * 19", 7, 133)]
+    #[case("old + old", 7, 14)]
+    #[case("old - 3", 7, 4)]
+    #[case("old * old", 7, 49)]
+    #[case("2 * old", 7, 14)]
+    fn test_operations(#[case] operation: &str, #[case] old: u64, #[case] expected: u64) {
+        let operation = super::operation.parse(operation).unwrap();
+        assert_eq!(operation.apply(&old), expected);
+        assert_eq!(
+            operation.apply(&BigUint::from(old)),
+            BigUint::from(expected)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "below zero")]
+    fn test_negative_worry() {
+        super::operation.parse("old - 8").unwrap().apply(&7u64);
+    }
+
+    #[test]
+    fn test_parse() {
+        let puzzle = Puzzle::<u64>::new(NOTES);
+        assert_eq!(puzzle.monkeys.len(), 2);
+        assert_eq!(puzzle.monkeys[0].items, [79, 98]);
+        assert!(puzzle.monkeys[1].items.is_empty());
+        assert_eq!(
+            puzzle.monkeys[1].operation,
+            Operation {
+                left: Operand::Old,
+                operator: Operator::Add,
+                right: Operand::Old
+            }
+        );
+        assert_eq!((puzzle.monkeys[1].test, puzzle.monkeys[1].matches), (6, 0));
+    }
+
+    #[test]
+    fn test_solves_generated_notes() {
+        check(32, |rng| {
+            let notes = day11::input(rng, 20);
+            let puzzle = Puzzle::<u64>::new(&notes);
+            assert_eq!(puzzle.monkeys.len(), 8);
+            let items: usize = puzzle.monkeys.iter().map(|m| m.items.len()).sum();
+            assert_eq!(items, 20);
+
+            let mut exact = Puzzle::<BigUint>::new(&notes);
+            exact.run(20, &DivideBy(3));
+            assert_eq!(part_one(&notes), Some(exact.monkey_business()));
+            assert!(part_two(&notes).is_some());
+        });
+    }
+
+    #[rstest]
+    #[case("If true: throw to monkey 1", "If true: throw to monkey 2", Error::TargetOutOfRange { monkey: 0, target: 2 })]
+    #[case("Monkey 1:", "Monkey 3:", Error::UnexpectedMonkey { expected: 1, found: 3 })]
+    #[case("divisible by 6", "divisible by 0", Error::ZeroDivisor { monkey: 1 })]
+    fn test_invalid_notes(#[case] from: &str, #[case] to: &str, #[case] expected: Error) {
+        let notes = NOTES.replacen(from, to, 1);
+        assert_eq!(Puzzle::<u64>::parse(&notes).err(), Some(expected));
+    }
+
+    #[rstest]
+    #[case("  Test: divisible by 4\n", "", "invalid Test")]
+    #[case("old * 19", "old / 19", "expected `+`, `-` or `*`")]
+    #[case("old * 19", "new * 19", "expected `old` or a number")]
+    #[case(
+        "If false: throw to monkey 1",
+        "If false: throw monkey 1",
+        "invalid If false"
+    )]
+    #[case("79, 98", "79, x", "invalid Starting items")]
+    fn test_syntax_errors(#[case] from: &str, #[case] to: &str, #[case] expected: &str) {
+        let notes = NOTES.replacen(from, to, 1);
+        let Err(Error::Syntax(error)) = Puzzle::<u64>::parse(&notes) else {
+            panic!("expected a syntax error");
+        };
+        assert!(error.contains(expected), "{error}");
+    }
+
+    #[test]
+    fn test_big_integers_without_relief() {
+        // the counts the puzzle description gives after 20 rounds of part 2.
+        let mut exact = example::<BigUint>();
+        exact.run(20, &NoRelief);
+        assert_eq!(inspected(&exact), [99, 97, 8, 103]);
+
+        let mut reduced = example::<u64>();
+        let relief = ModuloLcm::of(&reduced);
+        reduced.run(20, &relief);
+        assert_eq!(inspected(&reduced), inspected(&exact));
+    }
+
+    #[test]
+    #[should_panic(expected = "overflows")]
+    fn test_u64_overflow_without_relief() {
+        example::<u64>().run(20, &NoRelief);
+    }
+
+    #[test]
+    fn test_big_integers_match_u64() {
+        let mut big = example::<BigUint>();
+        big.run(20, &DivideBy(3));
+        let mut small = example::<u64>();
+        small.run(20, &DivideBy(3));
+        assert_eq!(inspected(&big), inspected(&small));
+        assert_eq!(big.monkey_business(), 10605);
+    }
 
     #[test]
     fn test_part_one() {
@@ -1,12 +1,16 @@
-advent_of_code::solution!(12);
+advent_of_code::solution!(12, parse = Maze::new);
+
+use std::collections::VecDeque;
 
 use pathfinding::prelude::*;
 
+type Pos = (usize, usize);
+
 #[derive(Debug)]
-struct Maze {
+pub struct Maze {
     cells: Matrix<char>,
-    start: (usize, usize),
-    end: (usize, usize),
+    start: Pos,
+    end: Pos,
 }
 
 impl Maze {
@@ -28,59 +32,172 @@ impl Maze {
         Self { cells, start, end }
     }
 
-    fn neighbours(&self, pos: (usize, usize)) -> Vec<((usize, usize), usize)> {
-        let height = *self.cells.get(pos).expect("to have a height") as i8;
+    fn height(&self, pos: Pos) -> i8 {
+        *self.cells.get(pos).expect("to have a height") as i8
+    }
+
+    /// Whether one step can go from `from` to the adjacent `to`: at most one higher, any lower.
+    fn can_climb(&self, from: Pos, to: Pos) -> bool {
+        self.height(to) - self.height(from) <= 1
+    }
+
+    fn neighbours(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
         self.cells
             .neighbours(pos, false)
-            .filter(|&neighbour| {
-                let n_height = *self.cells.get(neighbour).expect("neighbour exists") as i8;
-                let diff = n_height - height;
-                diff <= 1
-            })
-            .map(|neighbour| (neighbour, 1))
-            .collect()
+            .filter(move |&neighbour| self.can_climb(pos, neighbour))
+    }
+
+    /// Returns a shortest path from `S` to `E`, both included.
+    fn shortest_path(&self) -> Option<Vec<Pos>> {
+        bfs(&self.start, |&p| self.neighbours(p), |&p| p == self.end)
     }
 
-    fn shortest_path(&self) -> usize {
-        let (_path, cost) = dijkstra(&self.start, |&p| self.neighbours(p), |&p| p == self.end)
-            .expect("there will be a path");
-        cost
+    /// Walks backwards from `E` to find how many steps every square needs to reach it.
+    fn distances_to_end(&self) -> DistanceField {
+        let mut distances = Matrix::new(self.cells.rows, self.cells.columns, None);
+        distances[self.end] = Some(0);
+        let mut queue = VecDeque::from([(self.end, 0)]);
+        while let Some((pos, distance)) = queue.pop_front() {
+            for previous in self.cells.neighbours(pos, false) {
+                if distances[previous].is_none() && self.can_climb(previous, pos) {
+                    distances[previous] = Some(distance + 1);
+                    queue.push_back((previous, distance + 1));
+                }
+            }
+        }
+        DistanceField { distances }
     }
 
-    fn shortest_scenic_path(&self) -> usize {
+    fn shortest_scenic_path(&self) -> Option<usize> {
+        let field = self.distances_to_end();
         self.cells
             .items()
-            .filter_map(|((r, c), &height)| if height == 'a' { Some((r, c)) } else { None })
-            .filter_map(|start| dijkstra(&start, |&p| self.neighbours(p), |&p| p == self.end))
-            .map(|(_path, cost)| cost)
+            .filter(|&(_, &height)| height == 'a')
+            .filter_map(|(pos, _)| field.distances[pos])
             .min()
-            .expect("to find at least one")
+    }
+
+    /// Draws the path over the height map with arrows, like the puzzle description does.
+    #[cfg(any(test, feature = "demo"))]
+    fn render_path(&self, path: &[Pos]) -> String {
+        let mut picture = Matrix::new(self.cells.rows, self.cells.columns, '.');
+        for step in path.windows(2) {
+            let ((r0, c0), (r1, c1)) = (step[0], step[1]);
+            picture[step[0]] = if c1 > c0 {
+                '>'
+            } else if c1 < c0 {
+                '<'
+            } else if r1 > r0 {
+                'v'
+            } else {
+                '^'
+            };
+        }
+        if let Some(&last) = path.last() {
+            picture[last] = 'E';
+        }
+        let lines: Vec<String> = picture.iter().map(|row| row.iter().collect()).collect();
+        lines.join("\n")
+    }
+}
+
+/// Steps every square needs to reach `E`, or `None` when it cannot get there.
+#[derive(Debug)]
+struct DistanceField {
+    distances: Matrix<Option<usize>>,
+}
+
+#[cfg(any(test, feature = "demo"))]
+impl DistanceField {
+    /// Encodes the field as a binary PGM (`P5`) heat map: white at `E`, darker further away,
+    /// and black for squares that cannot reach it.
+    fn to_pgm(&self) -> Vec<u8> {
+        let furthest = self
+            .distances
+            .values()
+            .flatten()
+            .max()
+            .copied()
+            .unwrap_or(0);
+        let levels = self.distances.values().map(|distance| match distance {
+            Some(d) => (255 - d * 254 / furthest.max(1)) as u8,
+            None => 0,
+        });
+        advent_of_code::framebuffer::pgm(self.distances.columns, self.distances.rows, levels)
     }
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let maze = Maze::new(input);
-    Some(maze.shortest_path())
+pub fn part_one(maze: &Maze) -> Option<usize> {
+    let path = maze.shortest_path()?;
+    #[cfg(feature = "demo")]
+    println!("{}\n", maze.render_path(&path));
+    Some(path.len() - 1)
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let maze = Maze::new(input);
-    Some(maze.shortest_scenic_path())
+pub fn part_two(maze: &Maze) -> Option<usize> {
+    #[cfg(feature = "demo")]
+    {
+        let path = std::env::temp_dir().join("day12-distances.pgm");
+        match std::fs::write(&path, maze.distances_to_end().to_pgm()) {
+            Ok(()) => println!("Heat map written to {}", path.display()),
+            Err(e) => println!("Could not write {}: {e}", path.display()),
+        }
+    }
+    maze.shortest_scenic_path()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn example() -> Maze {
+        Maze::new(&advent_of_code::template::read_file("examples", DAY))
+    }
+
+    #[test]
+    fn test_shortest_path() {
+        let maze = example();
+        let path = maze.shortest_path().unwrap();
+        assert_eq!(path.first(), Some(&maze.start));
+        assert_eq!(path.last(), Some(&maze.end));
+        assert!(path
+            .windows(2)
+            .all(|step| maze.neighbours(step[0]).any(|next| next == step[1])));
+        assert_eq!(
+            maze.render_path(&path),
+            ">>vv<<<<\n..vvv<<^\n..vv>E^^\n..v>>>^^\n..>>>>>^"
+        );
+    }
+
+    #[test]
+    fn test_distance_field() {
+        let maze = example();
+        let field = maze.distances_to_end();
+        assert_eq!(field.distances[maze.end], Some(0));
+        assert_eq!(field.distances[maze.start], Some(31));
+        assert!(field.distances.values().all(Option::is_some));
+
+        let pgm = field.to_pgm();
+        assert!(pgm.starts_with(b"P5\n8 5\n255\n"));
+        assert_eq!(pgm.len(), b"P5\n8 5\n255\n".len() + 40);
+        assert_eq!(pgm[b"P5\n8 5\n255\n".len() + 2 * 8 + 5], 255);
+
+        let cliff = Maze::new("Sz\nzE");
+        let field = cliff.distances_to_end();
+        assert_eq!(field.distances[cliff.start], None);
+        assert_eq!(cliff.shortest_path(), None);
+        assert_eq!(field.to_pgm()[b"P5\n2 2\n255\n".len()..], [0, 1, 1, 255]);
+    }
+
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&example());
         assert_eq!(result, Some(31));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&example());
         assert_eq!(result, Some(29));
     }
 }
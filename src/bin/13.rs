@@ -1,6 +1,6 @@
 advent_of_code::solution!(13);
 
-use std::cmp::Ordering;
+use std::{cmp::Ordering, env, fmt, sync::OnceLock};
 
 use winnow::prelude::*;
 use winnow::{
@@ -8,6 +8,43 @@ use winnow::{
     combinator::{alt, delimited, separated},
 };
 
+/// How packets are compared. Read from `AOC_PACKET_COMPARE` (`tree` or `bytes`), so each mode
+/// can be benched with e.g. `AOC_PACKET_COMPARE=bytes cargo time 13 --sizes 1000,10000,100000`.
+/// `test_bench_modes` compares both modes side by side.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Compare {
+    /// Parses every packet into a [`Packet`] tree first.
+    #[default]
+    Tree,
+    /// Walks the raw lines side by side with a [`Cursor`] each, without parsing them.
+    Bytes,
+}
+
+impl Compare {
+    /// The mode of this run. The variable is only read by the first call, which the runner does
+    /// not count among the benched samples.
+    fn current() -> Self {
+        static CURRENT: OnceLock<Compare> = OnceLock::new();
+        *CURRENT.get_or_init(Self::from_env)
+    }
+
+    fn from_env() -> Self {
+        match env::var("AOC_PACKET_COMPARE") {
+            Err(_) => Self::default(),
+            Ok(s) => match s.trim() {
+                "tree" => Self::Tree,
+                "bytes" => Self::Bytes,
+                s => {
+                    eprintln!(
+                        "Ignoring invalid AOC_PACKET_COMPARE \"{s}\", expected \"tree\" or \"bytes\"."
+                    );
+                    Self::default()
+                }
+            },
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum Packet {
     Integer(u32),
@@ -36,28 +73,127 @@ impl Packet {
     }
 }
 
+/// Writes the packet in the input syntax, so parsing the output gives the same packet back.
+impl fmt::Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Packet::Integer(n) => write!(f, "{n}"),
+            Packet::List(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
 impl PartialOrd for Packet {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
 }
 
+/// Lists compare item by item, then by length. An integer compared to a list is treated as a
+/// list of just that integer, which a one element slice of the packet stands in for.
 impl Ord for Packet {
     fn cmp(&self, other: &Self) -> Ordering {
         use Packet::*;
         match (self, other) {
             (Integer(l), Integer(r)) => l.cmp(r),
-            (List(l), List(r)) => {
-                for (l, r) in l.iter().zip(r.iter()) {
-                    let ord = l.cmp(r);
-                    if ord != Ordering::Equal {
-                        return ord;
-                    }
+            (List(l), List(r)) => l.cmp(r),
+            (Integer(_), List(r)) => std::slice::from_ref(self).cmp(r),
+            (List(l), Integer(_)) => l.as_slice().cmp(std::slice::from_ref(other)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Token {
+    Open,
+    Close,
+    Integer(u32),
+    End,
+}
+
+/// Reads the tokens of a packet straight from its bytes.
+///
+/// An integer compared to a list is wrapped into a list on the fly: the cursor yields an opening
+/// bracket in its place, then the integer, then the closing brackets it owes.
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    /// Integer to yield again after it was wrapped into a list.
+    wrapped: Option<u32>,
+    /// Closing brackets owed for wrapped integers, yielded right after the integer.
+    closes: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            pos: 0,
+            wrapped: None,
+            closes: 0,
+        }
+    }
+
+    fn next(&mut self) -> Token {
+        if let Some(n) = self.wrapped.take() {
+            return Token::Integer(n);
+        }
+        if self.closes > 0 {
+            self.closes -= 1;
+            return Token::Close;
+        }
+        while self.bytes.get(self.pos) == Some(&b',') {
+            self.pos += 1;
+        }
+        let Some(&byte) = self.bytes.get(self.pos) else {
+            return Token::End;
+        };
+        self.pos += 1;
+        match byte {
+            b'[' => Token::Open,
+            b']' => Token::Close,
+            b'0'..=b'9' => {
+                let mut n = u32::from(byte - b'0');
+                while let Some(&digit @ b'0'..=b'9') = self.bytes.get(self.pos) {
+                    n = n * 10 + u32::from(digit - b'0');
+                    self.pos += 1;
                 }
-                l.len().cmp(&r.len())
+                Token::Integer(n)
             }
-            (Integer(_), List(_)) => List(vec![self.clone()]).cmp(other),
-            (List(_), Integer(_)) => self.cmp(&List(vec![other.clone()])),
+            _ => panic!("unexpected byte {:?} in packet", byte as char),
+        }
+    }
+
+    /// Wraps the integer just read into a list, whose opening bracket counts as read.
+    fn wrap(&mut self, n: u32) {
+        self.wrapped = Some(n);
+        self.closes += 1;
+    }
+}
+
+/// Compares two packets in their input syntax without parsing them or allocating.
+fn compare_bytes(left: &[u8], right: &[u8]) -> Ordering {
+    use Token::*;
+    let (mut left, mut right) = (Cursor::new(left), Cursor::new(right));
+    loop {
+        match (left.next(), right.next()) {
+            (Open, Open) | (Close, Close) => {}
+            (Integer(l), Integer(r)) if l == r => {}
+            (Integer(l), Integer(r)) => return l.cmp(&r),
+            (Integer(l), Open) => left.wrap(l),
+            (Open, Integer(r)) => right.wrap(r),
+            (End, End) => return Ordering::Equal,
+            (End | Close, _) => return Ordering::Less,
+            (_, End | Close) => return Ordering::Greater,
         }
     }
 }
@@ -66,6 +202,14 @@ fn correct_order(left: &Packet, right: &Packet) -> bool {
     left.cmp(right) != Ordering::Greater
 }
 
+/// Splits the input into the two lines of each pair.
+fn pairs(s: &str) -> impl Iterator<Item = (&str, &str)> {
+    s.split("\n\n").map(|pair| {
+        let (left, right) = pair.split_once('\n').expect("pair is two lines");
+        (left, right.trim_end())
+    })
+}
+
 #[derive(Debug)]
 struct Packets {
     packets: Vec<(Packet, Packet)>,
@@ -73,14 +217,8 @@ struct Packets {
 
 impl Packets {
     fn new(s: &str) -> Self {
-        let packets = s
-            .split("\n\n")
-            .map(|pair| {
-                let (left, right) = pair.split_once('\n').expect("pair is two lines");
-                let left = Packet::new(left);
-                let right = Packet::new(right);
-                (left, right)
-            })
+        let packets = pairs(s)
+            .map(|(left, right)| (Packet::new(left), Packet::new(right)))
             .collect();
         Self { packets }
     }
@@ -95,31 +233,65 @@ impl Packets {
     }
 }
 
+/// Multiplies the positions, counted from 1, that the two dividers take once all packets are sorted.
+/// Counting the packets that sort before each divider is enough, no need to sort them.
+fn decoder_key<P>(
+    packets: impl Iterator<Item = P>,
+    [first, second]: [P; 2],
+    cmp: impl Fn(&P, &P) -> Ordering,
+) -> usize {
+    let (mut first_position, mut second_position) = (1, 2);
+    for packet in packets {
+        if cmp(&packet, &first) == Ordering::Less {
+            first_position += 1;
+        }
+        if cmp(&packet, &second) == Ordering::Less {
+            second_position += 1;
+        }
+    }
+    first_position * second_position
+}
+
+fn solve_one(input: &str, compare: Compare) -> usize {
+    match compare {
+        Compare::Tree => Packets::new(input).correct_order(),
+        Compare::Bytes => pairs(input)
+            .enumerate()
+            .filter(|(_, (left, right))| {
+                compare_bytes(left.as_bytes(), right.as_bytes()) != Ordering::Greater
+            })
+            .map(|(i, _)| i + 1)
+            .sum(),
+    }
+}
+
+fn solve_two(input: &str, compare: Compare) -> usize {
+    let lines = input.lines().filter(|l| !l.is_empty());
+    let dividers = ["[[2]]", "[[6]]"];
+    match compare {
+        Compare::Tree => decoder_key(
+            lines.map(Packet::new),
+            dividers.map(Packet::new),
+            Packet::cmp,
+        ),
+        Compare::Bytes => decoder_key(lines, dividers, |l, r| {
+            compare_bytes(l.as_bytes(), r.as_bytes())
+        }),
+    }
+}
+
 pub fn part_one(input: &str) -> Option<usize> {
-    let packets = Packets::new(input);
-    Some(packets.correct_order())
+    Some(solve_one(input, Compare::current()))
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let mut packets: Vec<_> = input
-        .lines()
-        .filter(|l| !l.is_empty())
-        .map(Packet::new)
-        .collect();
-    let divider_one = Packet::new("[[2]]");
-    let divider_two = Packet::new("[[6]]");
-    packets.push(divider_one.clone());
-    packets.push(divider_two.clone());
-    packets.sort();
-    let first = packets.iter().position(|p| *p == divider_one).unwrap();
-    let second = packets.iter().position(|p| *p == divider_two).unwrap();
-
-    Some((first + 1) * (second + 1))
+    Some(solve_two(input, Compare::current()))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::{generators::day13, property::check};
 
     #[test]
     fn test_correct_order_one() {
@@ -177,6 +349,125 @@ mod tests {
         assert!(!correct_order(&left, &right));
     }
 
+    #[test]
+    fn test_order_is_antisymmetric() {
+        check(256, |rng| {
+            let left = Packet::new(&day13::list(rng, 4));
+            let right = Packet::new(&day13::list(rng, 4));
+            assert_eq!(left.cmp(&left), Ordering::Equal);
+            assert_eq!(left.cmp(&right), right.cmp(&left).reverse());
+        });
+    }
+
+    #[test]
+    fn test_order_is_transitive() {
+        check(256, |rng| {
+            let mut packets: Vec<_> = (0..3).map(|_| Packet::new(&day13::list(rng, 3))).collect();
+            packets.sort();
+            assert!(packets[0] <= packets[1] && packets[1] <= packets[2]);
+            assert!(packets[0] <= packets[2]);
+        });
+    }
+
+    #[test]
+    fn test_integer_compares_as_list() {
+        check(64, |rng| {
+            let n = rng.range(0..=10);
+            let integer = Packet::new(&n.to_string());
+            let list = Packet::new(&format!("[{n}]"));
+            assert_eq!(integer.cmp(&list), Ordering::Equal);
+        });
+    }
+
+    #[test]
+    fn test_display_roundtrips() {
+        for s in ["[]", "[[[]]]", "[1,[2,[3,[4,[5,6,7]]]],8,9]", "[10,[],[0]]"] {
+            assert_eq!(Packet::new(s).to_string(), s);
+        }
+        check(256, |rng| {
+            let s = day13::list(rng, 4);
+            assert_eq!(Packet::new(&s).to_string(), s);
+        });
+    }
+
+    #[test]
+    fn test_bytes_compare_like_trees() {
+        let cases = [
+            ("[1,1,3,1,1]", "[1,1,5,1,1]"),
+            ("[[1],[2,3,4]]", "[[1],4]"),
+            ("[9]", "[[8,7,6]]"),
+            ("[[4,4],4,4]", "[[4,4],4,4,4]"),
+            ("[[[]]]", "[[]]"),
+            ("[10]", "[[[10]],1]"),
+            ("[[[10]]]", "[10]"),
+            ("[[[3],2]]", "[3]"),
+        ];
+        for (left, right) in cases {
+            let expected = Packet::new(left).cmp(&Packet::new(right));
+            assert_eq!(compare_bytes(left.as_bytes(), right.as_bytes()), expected);
+            assert_eq!(
+                compare_bytes(right.as_bytes(), left.as_bytes()),
+                expected.reverse()
+            );
+        }
+        check(256, |rng| {
+            let (left, right) = (day13::list(rng, 4), day13::list(rng, 4));
+            assert_eq!(
+                compare_bytes(left.as_bytes(), right.as_bytes()),
+                Packet::new(&left).cmp(&Packet::new(&right)),
+                "{left} vs {right}"
+            );
+        });
+    }
+
+    #[test]
+    fn test_modes_agree() {
+        let example = advent_of_code::template::read_file("examples", DAY);
+        assert_eq!(solve_one(&example, Compare::Bytes), 13);
+        assert_eq!(solve_two(&example, Compare::Bytes), 140);
+
+        check(32, |rng| {
+            let input = day13::input(rng, 20, 4);
+            assert_eq!(
+                solve_one(&input, Compare::Tree),
+                solve_one(&input, Compare::Bytes)
+            );
+            assert_eq!(
+                solve_two(&input, Compare::Tree),
+                solve_two(&input, Compare::Bytes)
+            );
+        });
+    }
+
+    /// Times both modes on large generated inputs. Run with
+    /// `cargo test --release --bin 13 test_bench_modes -- --ignored --nocapture`.
+    #[test]
+    #[ignore = "benchmark, slow in debug builds"]
+    fn test_bench_modes() {
+        use advent_of_code::{generators::generate, template::commands::generate::DEFAULT_SEED};
+        use std::{hint::black_box, time::Instant};
+
+        let time = |f: &dyn Fn() -> usize| {
+            let timer = Instant::now();
+            let result = black_box(f());
+            (result, timer.elapsed())
+        };
+
+        println!("Pairs    Mode   Part 1      Part 2");
+        for pairs in [1_000, 10_000, 100_000] {
+            let input = generate(DAY, pairs, DEFAULT_SEED).unwrap();
+            let mut results = vec![];
+            for compare in [Compare::Tree, Compare::Bytes] {
+                let (one, one_time) = time(&|| solve_one(&input, compare));
+                let (two, two_time) = time(&|| solve_two(&input, compare));
+                let mode = format!("{compare:?}");
+                println!("{pairs:<8} {mode:<6} {one_time:<11.1?} {two_time:.1?}");
+                results.push((one, two));
+            }
+            assert_eq!(results[0], results[1]);
+        }
+    }
+
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
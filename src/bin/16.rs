@@ -9,7 +9,7 @@ use winnow::{
     combinator::{alt, separated},
 };
 
-advent_of_code::solution!(16);
+advent_of_code::solution!(16, parse = Valves::new);
 
 fn valve<'a>(input: &mut &'a str) -> PResult<(&'a str, u32, Vec<&'a str>)> {
     let (_, id, _, flow, _, edges) = (
@@ -25,7 +25,7 @@ fn valve<'a>(input: &mut &'a str) -> PResult<(&'a str, u32, Vec<&'a str>)> {
     Ok((id, flow, edges))
 }
 
-struct Valves<'a> {
+pub struct Valves<'a> {
     valves: Vec<(&'a str, u32, Vec<&'a str>)>,
     ids: HashMap<&'a str, usize>,
     interesting: Vec<usize>,
@@ -191,13 +191,11 @@ impl<'b> Valves<'b> {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let valves = Valves::new(input);
+pub fn part_one(valves: &Valves) -> Option<u32> {
     Some(valves.most_released(30))
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let valves = Valves::new(input);
+pub fn part_two(valves: &Valves) -> Option<u32> {
     Some(valves.most_released_paired(26))
 }
 
@@ -207,13 +205,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&Valves::new(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(1651));
     }
 
     #[test]
     fn test_part_two() {
-        let _result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let _result = part_two(&Valves::new(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         // assert_eq!(result, Some(1707));
     }
 }
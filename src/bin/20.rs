@@ -27,7 +27,7 @@ pub fn part_one(input: &str) -> Option<i64> {
     let zero = mixed.iter().position(|&n| n == 0).expect("zero");
     Some(
         (1..=3)
-            .map(|i| mixed[(zero + i * 1000) % mixed.len() as usize])
+            .map(|i| mixed[(zero + i * 1000) % mixed.len()])
             .sum(),
     )
 }
@@ -41,7 +41,7 @@ pub fn part_two(input: &str) -> Option<i64> {
     let zero = mixed.iter().position(|&n| n == 0).expect("zero");
     Some(
         (1..=3)
-            .map(|i| mixed[(zero + i * 1000) % mixed.len() as usize])
+            .map(|i| mixed[(zero + i * 1000) % mixed.len()])
             .sum(),
     )
 }
@@ -49,6 +49,7 @@ pub fn part_two(input: &str) -> Option<i64> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::{generators::day20, property::check};
 
     #[test]
     fn test_mix() {
@@ -60,6 +61,20 @@ mod tests {
         assert_eq!(result, [1, 2, -3, 4, 0, 3, -2]);
     }
 
+    #[test]
+    fn test_mix_preserves_numbers() {
+        check(128, |rng| {
+            let len = rng.index(2..=50);
+            let numbers = day20::numbers(rng, len);
+            let rounds = rng.index(1..=3);
+            let mut mixed = mix(&numbers, rounds);
+            let mut numbers = numbers;
+            mixed.sort_unstable();
+            numbers.sort_unstable();
+            assert_eq!(mixed, numbers);
+        });
+    }
+
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
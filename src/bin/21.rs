@@ -164,6 +164,16 @@ pub fn part_two(input: &str) -> Option<Value> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::{generators::day21, property::check};
+
+    #[test]
+    fn test_human_say_solves_riddle() {
+        check(256, |rng| {
+            let depth = rng.index(0..=12);
+            let riddle = day21::riddle(rng, depth);
+            assert_eq!(parse_monkeys(&riddle.input).human_say(), riddle.humn);
+        });
+    }
 
     #[test]
     fn test_part_one() {
@@ -1,5 +1,6 @@
 use std::collections::{HashMap, HashSet};
 
+use advent_of_code::framebuffer::Framebuffer;
 use itertools::Itertools;
 advent_of_code::solution!(23);
 
@@ -10,35 +11,7 @@ struct Elves {
 
 impl std::fmt::Display for Elves {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let (&min_row, &max_row) = self
-            .locations
-            .iter()
-            .map(|(r, _)| r)
-            .minmax()
-            .into_option()
-            .unwrap();
-        let (&min_col, &max_col) = self
-            .locations
-            .iter()
-            .map(|(_, c)| c)
-            .minmax()
-            .into_option()
-            .unwrap();
-        for row in min_row..=max_row {
-            for col in min_col..=max_col {
-                write!(
-                    f,
-                    "{}",
-                    if self.locations.contains(&(row, col)) {
-                        '#'
-                    } else {
-                        '.'
-                    }
-                )?;
-            }
-            writeln!(f)?;
-        }
-        Ok(())
+        writeln!(f, "{}", self.snapshot())
     }
 }
 
@@ -119,6 +92,30 @@ impl Elves {
         true
     }
 
+    /// Draws the smallest rectangle that contains all elves.
+    fn snapshot(&self) -> Framebuffer {
+        let (&min_row, &max_row) = self
+            .locations
+            .iter()
+            .map(|(r, _)| r)
+            .minmax()
+            .into_option()
+            .unwrap();
+        let (&min_col, &max_col) = self
+            .locations
+            .iter()
+            .map(|(_, c)| c)
+            .minmax()
+            .into_option()
+            .unwrap();
+        let width = min_col.abs_diff(max_col) + 1;
+        let height = min_row.abs_diff(max_row) + 1;
+        Framebuffer::from_fn(width, height, |x, y| {
+            self.locations
+                .contains(&(min_row + y as isize, min_col + x as isize))
+        })
+    }
+
     fn empty_tiles(&self) -> usize {
         let (&min_row, &max_row) = self
             .locations
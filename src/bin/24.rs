@@ -3,7 +3,7 @@ use pathfinding::directed::dijkstra::dijkstra;
 
 use std::collections::HashSet;
 
-advent_of_code::solution!(24);
+advent_of_code::solution!(24, parse = Maze::new);
 
 #[derive(Debug, Hash, PartialEq, Eq)]
 enum Direction {
@@ -33,7 +33,7 @@ struct Flake {
 }
 
 #[derive(Debug)]
-struct Maze {
+pub struct Maze {
     rows: isize,
     columns: isize,
     flakes: HashSet<Flake>,
@@ -165,13 +165,11 @@ impl Maze {
     }
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let maze = Maze::new(input);
+pub fn part_one(maze: &Maze) -> Option<usize> {
     Some(maze.shortest_path())
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let maze = Maze::new(input);
+pub fn part_two(maze: &Maze) -> Option<usize> {
     Some(maze.out_in_out())
 }
 
@@ -275,13 +273,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&Maze::new(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(18));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&Maze::new(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(54));
     }
 }
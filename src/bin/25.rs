@@ -39,6 +39,7 @@ pub fn part_one(input: &str) -> Option<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::{generators::day25, property::check};
     use rstest::rstest;
 
     #[rstest]
@@ -62,6 +63,23 @@ mod tests {
         assert_eq!(to_snafu(decimal), snafu);
     }
 
+    #[test]
+    fn test_snafu_roundtrip() {
+        check(256, |rng| {
+            let digits = rng.index(1..=20);
+            let snafu = day25::snafu(rng, digits);
+            assert_eq!(to_snafu(from_snafu(&snafu)), snafu);
+        });
+    }
+
+    #[test]
+    fn test_decimal_roundtrip() {
+        check(256, |rng| {
+            let decimal = rng.range(1..=i64::MAX / 5);
+            assert_eq!(from_snafu(&to_snafu(decimal)), decimal);
+        });
+    }
+
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
@@ -0,0 +1,250 @@
+//! A monochrome framebuffer for puzzles that draw pictures, such as the CRT of day 10.
+//!
+//! Renders as terminal text, either one character or half a character per pixel, and exports to
+//! the netpbm formats PBM and PGM, which most image viewers open.
+use std::{
+    fmt::{self, Display},
+    fs, io,
+    path::Path,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Framebuffer {
+    width: usize,
+    height: usize,
+    /// Row by row, from the top left.
+    pixels: Vec<bool>,
+}
+
+impl Framebuffer {
+    /// Creates a framebuffer with all pixels off.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![false; width * height],
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> bool) -> Self {
+        let pixels = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| f(x, y))
+            .collect();
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns whether the pixel is lit, or `false` outside of the framebuffer.
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height && self.pixels[y * self.width + x]
+    }
+
+    /// Sets a pixel. Panics if it lies outside of the framebuffer.
+    pub fn set(&mut self, x: usize, y: usize, lit: bool) {
+        assert!(
+            x < self.width && y < self.height,
+            "pixel ({x}, {y}) outside of a {}x{} framebuffer",
+            self.width,
+            self.height
+        );
+        self.pixels[y * self.width + x] = lit;
+    }
+
+    /// Sets the pixel at an index in row order, e.g. the cycle of a CRT beam.
+    /// Returns `false` if the index lies past the last pixel.
+    pub fn set_index(&mut self, index: usize, lit: bool) -> bool {
+        match self.pixels.get_mut(index) {
+            Some(pixel) => {
+                *pixel = lit;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[bool]> {
+        self.pixels.chunks(self.width.max(1))
+    }
+
+    /// Returns a copy with every pixel blown up to a `factor` by `factor` square.
+    pub fn scaled(&self, factor: usize) -> Self {
+        Self::from_fn(self.width * factor, self.height * factor, |x, y| {
+            self.get(x / factor, y / factor)
+        })
+    }
+
+    /// Renders one character per pixel, one line per row.
+    pub fn to_text(&self, lit: char, unlit: char) -> String {
+        let lines: Vec<String> = self
+            .rows()
+            .map(|row| row.iter().map(|&p| if p { lit } else { unlit }).collect())
+            .collect();
+        lines.join("\n")
+    }
+
+    /// Renders two rows per line with Unicode half blocks, which keeps pixels roughly square in a terminal.
+    pub fn to_half_blocks(&self) -> String {
+        let lines: Vec<String> = (0..self.height)
+            .step_by(2)
+            .map(|y| {
+                (0..self.width)
+                    .map(|x| match (self.get(x, y), self.get(x, y + 1)) {
+                        (true, true) => '█',
+                        (true, false) => '▀',
+                        (false, true) => '▄',
+                        (false, false) => ' ',
+                    })
+                    .collect()
+            })
+            .collect();
+        lines.join("\n")
+    }
+
+    /// Encodes a binary PBM (`P4`) image. Lit pixels are black.
+    pub fn to_pbm(&self) -> Vec<u8> {
+        let mut out = format!("P4\n{} {}\n", self.width, self.height).into_bytes();
+        for row in self.rows() {
+            // every row starts on a new byte, most significant bit first.
+            for byte in row.chunks(8) {
+                let bits = byte
+                    .iter()
+                    .enumerate()
+                    .fold(0u8, |acc, (i, &p)| acc | (u8::from(p) << (7 - i)));
+                out.push(bits);
+            }
+        }
+        out
+    }
+
+    /// Encodes a binary PGM (`P5`) image. Lit pixels are black, like in [`Framebuffer::to_pbm`].
+    pub fn to_pgm(&self) -> Vec<u8> {
+        pgm(
+            self.width,
+            self.height,
+            self.pixels.iter().map(|&p| if p { 0 } else { 255 }),
+        )
+    }
+
+    /// Writes the framebuffer as an image, in the format given by the `.pbm` or `.pgm` extension.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let bytes = match path.extension().and_then(|e| e.to_str()) {
+            Some("pbm") => self.to_pbm(),
+            Some("pgm") => self.to_pgm(),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("{} is not a .pbm or .pgm file", path.display()),
+                ))
+            }
+        };
+        fs::write(path, bytes)
+    }
+}
+
+/// Encodes a binary PGM (`P5`) image from gray levels given row by row, from 0 for black to 255 for white.
+/// Useful for pictures with more than two shades, such as heat maps.
+pub fn pgm(width: usize, height: usize, pixels: impl Iterator<Item = u8>) -> Vec<u8> {
+    let mut out = format!("P5\n{width} {height}\n255\n").into_bytes();
+    out.extend(pixels);
+    out
+}
+
+/// Renders lit pixels as `#` and unlit ones as `.`, like the puzzle descriptions do.
+impl Display for Framebuffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_text('#', '.'))
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{pgm, Framebuffer};
+
+    fn glider() -> Framebuffer {
+        let mut fb = Framebuffer::new(3, 3);
+        for (x, y) in [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)] {
+            fb.set(x, y, true);
+        }
+        fb
+    }
+
+    #[test]
+    fn renders_text() {
+        assert_eq!(glider().to_string(), ".#.\n..#\n###");
+        assert_eq!(glider().to_text('█', ' '), " █ \n  █\n███");
+        assert_eq!(Framebuffer::new(0, 0).to_string(), "");
+    }
+
+    #[test]
+    fn renders_half_blocks() {
+        assert_eq!(glider().to_half_blocks(), " ▀▄\n▀▀▀");
+    }
+
+    #[test]
+    fn sets_pixels() {
+        let mut fb = Framebuffer::new(4, 2);
+        assert!(fb.set_index(5, true));
+        assert!(!fb.set_index(8, true));
+        assert!(fb.get(1, 1));
+        assert!(!fb.get(4, 1));
+        assert_eq!(Framebuffer::from_fn(4, 2, |x, y| (x, y) == (1, 1)), fb);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_outside() {
+        Framebuffer::new(2, 2).set(2, 0, true);
+    }
+
+    #[test]
+    fn scales() {
+        let fb = Framebuffer::from_fn(2, 1, |x, _| x == 0);
+        assert_eq!(fb.scaled(2).to_string(), "##..\n##..");
+    }
+
+    #[test]
+    fn encodes_pbm() {
+        assert_eq!(glider().to_pbm(), b"P4\n3 3\n\x40\x20\xe0");
+
+        // rows are padded to whole bytes.
+        let fb = Framebuffer::from_fn(9, 1, |x, _| x == 0 || x == 8);
+        assert_eq!(fb.to_pbm(), b"P4\n9 1\n\x80\x80");
+    }
+
+    #[test]
+    fn encodes_pgm() {
+        assert_eq!(
+            glider().to_pgm(),
+            b"P5\n3 3\n255\n\xff\x00\xff\xff\xff\x00\x00\x00\x00"
+        );
+    }
+
+    #[test]
+    fn encodes_gray_levels() {
+        assert_eq!(pgm(2, 1, [0, 128].into_iter()), b"P5\n2 1\n255\n\x00\x80");
+    }
+
+    #[test]
+    fn saves_by_extension() {
+        let dir = std::env::temp_dir();
+        let path = dir.join(format!("aoc-framebuffer-{}.pbm", std::process::id()));
+        glider().save(&path).unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), glider().to_pbm());
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(glider().save(dir.join("glider.png")).is_err());
+    }
+}
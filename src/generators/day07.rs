@@ -0,0 +1,91 @@
+use super::Rng;
+
+/// Capacity of the device in the puzzle is 70000000, of which 30000000 have to be free.
+/// Generated files add up to between half and all of this, so part 2 usually has to delete something.
+const TOTAL_SIZE: i64 = 64_000_000;
+
+const EXTENSIONS: [&str; 5] = ["", ".txt", ".dat", ".lst", ".log"];
+
+#[derive(Default)]
+struct Dir {
+    name: String,
+    dirs: Vec<usize>,
+    /// Names already taken by an entry of this directory.
+    names: Vec<String>,
+    files: Vec<(String, i64)>,
+}
+
+fn name(rng: &mut Rng, taken: &[String], extension: &str) -> String {
+    loop {
+        let len = rng.index(1..=6);
+        let stem: String = (0..len)
+            .map(|_| (b'a' + rng.below(26) as u8) as char)
+            .collect();
+        let name = format!("{stem}{extension}");
+        if !taken.contains(&name) {
+            return name;
+        }
+    }
+}
+
+fn emit(rng: &mut Rng, tree: &[Dir], dir: usize, lines: &mut Vec<String>) {
+    let dir = &tree[dir];
+
+    let mut entries: Vec<_> = dir
+        .dirs
+        .iter()
+        .map(|&d| format!("dir {}", tree[d].name))
+        .chain(
+            dir.files
+                .iter()
+                .map(|(name, size)| format!("{size} {name}")),
+        )
+        .collect();
+    rng.shuffle(&mut entries);
+    lines.push("$ ls".into());
+    lines.extend(entries);
+
+    let mut dirs = dir.dirs.clone();
+    rng.shuffle(&mut dirs);
+    for d in dirs {
+        lines.push(format!("$ cd {}", tree[d].name));
+        emit(rng, tree, d, lines);
+        lines.push("$ cd ..".into());
+    }
+}
+
+/// Generates the terminal transcript of exploring a random tree of `dirs` directories,
+/// listing every directory once, depth first.
+pub fn input(rng: &mut Rng, dirs: usize) -> String {
+    let mut tree = vec![Dir::default()];
+
+    for i in 1..dirs.max(1) {
+        let parent = rng.index(0..=i - 1);
+        let name = name(rng, &tree[parent].names, "");
+        tree[parent].names.push(name.clone());
+        tree[parent].dirs.push(i);
+        tree.push(Dir {
+            name,
+            ..Dir::default()
+        });
+    }
+
+    for dir in tree.iter_mut() {
+        for _ in 0..rng.index(0..=4) {
+            let extension = *rng.choose(&EXTENSIONS);
+            let name = name(rng, &dir.names, extension);
+            dir.names.push(name.clone());
+            dir.files.push((name, 0));
+        }
+    }
+
+    let files: usize = tree.iter().map(|dir| dir.files.len()).sum();
+    let max_size = (TOTAL_SIZE / files.max(1) as i64).max(2);
+    for (_, size) in tree.iter_mut().flat_map(|dir| dir.files.iter_mut()) {
+        *size = rng.range(max_size / 2..=max_size);
+    }
+
+    let mut lines = vec!["$ cd /".to_string()];
+    emit(rng, &tree, 0, &mut lines);
+    lines.join("\n")
+}
@@ -0,0 +1,62 @@
+use super::Rng;
+
+/// Distinct prime divisors, so the product part 2 reduces worry levels by stays small enough that
+/// `old * old` cannot overflow a `u64`.
+const DIVISORS: [u64; 8] = [2, 3, 5, 7, 11, 13, 17, 19];
+
+/// Largest multiplier of an `old * n` operation. Part 1 divides by 3 after every inspection, so
+/// multiplying by at most 3 never grows a worry level and only additions can, by less than they
+/// add. This keeps part 1 well inside a `u64` however often an item is passed around.
+const MAX_MULTIPLIER: u64 = 3;
+
+/// Generates the notes for 8 monkeys holding `items` items in total. Every monkey starts with at least one item.
+///
+/// At most one monkey squares worry levels, and no monkey throws to it, so every item is squared
+/// at most once during part 1.
+pub fn input(rng: &mut Rng, items: usize) -> String {
+    let monkeys = DIVISORS.len();
+    let squarer = rng.ratio(2, 3).then(|| rng.index(0..=monkeys - 1));
+    let mut divisors = DIVISORS;
+    rng.shuffle(&mut divisors);
+
+    let mut held: Vec<Vec<u64>> = vec![vec![]; monkeys];
+    for n in 0..items.max(monkeys) {
+        let monkey = if n < monkeys {
+            n
+        } else {
+            rng.index(0..=monkeys - 1)
+        };
+        held[monkey].push(rng.range(50..=99) as u64);
+    }
+
+    let notes: Vec<_> = (0..monkeys)
+        .map(|id| {
+            let operation = if squarer == Some(id) {
+                "old * old".to_string()
+            } else if rng.ratio(1, 2) {
+                format!("old + {}", rng.range(1..=8))
+            } else {
+                format!("old * {}", rng.range(2..=MAX_MULTIPLIER as i64))
+            };
+
+            let mut targets: Vec<_> = (0..monkeys)
+                .filter(|&other| other != id && Some(other) != squarer)
+                .collect();
+            rng.shuffle(&mut targets);
+
+            let items: Vec<_> = held[id].iter().map(u64::to_string).collect();
+
+            [
+                format!("Monkey {id}:"),
+                format!("  Starting items: {}", items.join(", ")),
+                format!("  Operation: new = {operation}"),
+                format!("  Test: divisible by {}", divisors[id]),
+                format!("    If true: throw to monkey {}", targets[0]),
+                format!("    If false: throw to monkey {}", targets[1]),
+            ]
+            .join("\n")
+        })
+        .collect();
+
+    notes.join("\n\n")
+}
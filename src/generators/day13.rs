@@ -0,0 +1,27 @@
+use super::Rng;
+
+/// Generates a packet of nested lists and integers, e.g. `[1,[2,[]],3]`.
+pub fn packet(rng: &mut Rng, depth: usize) -> String {
+    if depth > 0 && rng.ratio(2, 3) {
+        list(rng, depth)
+    } else {
+        rng.range(0..=10).to_string()
+    }
+}
+
+/// Generates a packet that is a list at the top level, as all packets in the puzzle input are.
+pub fn list(rng: &mut Rng, depth: usize) -> String {
+    let len = rng.index(0..=4);
+    let items: Vec<_> = (0..len)
+        .map(|_| packet(rng, depth.saturating_sub(1)))
+        .collect();
+    format!("[{}]", items.join(","))
+}
+
+/// Generates a puzzle input of `pairs` packet pairs.
+pub fn input(rng: &mut Rng, pairs: usize, depth: usize) -> String {
+    let pairs: Vec<_> = (0..pairs)
+        .map(|_| format!("{}\n{}", list(rng, depth), list(rng, depth)))
+        .collect();
+    pairs.join("\n\n")
+}
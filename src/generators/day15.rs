@@ -0,0 +1,20 @@
+use super::Rng;
+
+/// Generates `sensors` sensors, scattered over the area searched in part two.
+pub fn input(rng: &mut Rng, sensors: usize) -> String {
+    let lines: Vec<_> = (0..sensors)
+        .map(|_| {
+            let (x, y) = (rng.range(0..=4_000_000), rng.range(0..=4_000_000));
+            let distance = rng.range(100_000..=1_000_000);
+            let dx = rng.range(-distance..=distance);
+            let dy = distance - dx.abs();
+            let dy = if rng.ratio(1, 2) { dy } else { -dy };
+            format!(
+                "Sensor at x={x}, y={y}: closest beacon is at x={}, y={}",
+                x + dx,
+                y + dy
+            )
+        })
+        .collect();
+    lines.join("\n")
+}
@@ -0,0 +1,55 @@
+use super::Rng;
+
+/// The solution keeps valves in a 64 bit set and valves with flow in a 16 bit set.
+pub const MAX_VALVES: usize = 64;
+pub const MAX_FLOWING_VALVES: usize = 15;
+
+fn valve_name(i: usize) -> String {
+    let first = (b'A' + (i / 26) as u8) as char;
+    let second = (b'A' + (i % 26) as u8) as char;
+    format!("{first}{second}")
+}
+
+/// Generates a connected network of `valves` valves (at most [`MAX_VALVES`]), starting at `AA`.
+pub fn input(rng: &mut Rng, valves: usize) -> String {
+    let valves = valves.clamp(2, MAX_VALVES);
+
+    // random spanning tree, plus a few extra tunnels to create cycles.
+    let mut tunnels: Vec<Vec<usize>> = vec![vec![]; valves];
+    let mut connect = |a: usize, b: usize| {
+        if a != b && !tunnels[a].contains(&b) {
+            tunnels[a].push(b);
+            tunnels[b].push(a);
+        }
+    };
+    for i in 1..valves {
+        let j = rng.index(0..=i - 1);
+        connect(i, j);
+    }
+    for _ in 0..valves / 4 {
+        let (a, b) = (rng.index(0..=valves - 1), rng.index(0..=valves - 1));
+        connect(a, b);
+    }
+
+    let mut flowing: Vec<usize> = (1..valves).collect();
+    rng.shuffle(&mut flowing);
+    flowing.truncate((valves / 3).clamp(1, MAX_FLOWING_VALVES));
+
+    let lines: Vec<_> = (0..valves)
+        .map(|i| {
+            let flow = if flowing.contains(&i) {
+                rng.range(1..=25)
+            } else {
+                0
+            };
+            let names: Vec<_> = tunnels[i].iter().map(|&j| valve_name(j)).collect();
+            let tunnels = if names.len() == 1 {
+                format!("tunnel leads to valve {}", names[0])
+            } else {
+                format!("tunnels lead to valves {}", names.join(", "))
+            };
+            format!("Valve {} has flow rate={flow}; {tunnels}", valve_name(i))
+        })
+        .collect();
+    lines.join("\n")
+}
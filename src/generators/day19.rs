@@ -0,0 +1,20 @@
+use super::Rng;
+
+/// Generates `blueprints` blueprints with robot costs in the ranges seen in puzzle inputs.
+pub fn input(rng: &mut Rng, blueprints: usize) -> String {
+    let lines: Vec<_> = (1..=blueprints)
+        .map(|n| {
+            format!(
+                "Blueprint {n}: Each ore robot costs {} ore. Each clay robot costs {} ore. \
+                Each obsidian robot costs {} ore and {} clay. Each geode robot costs {} ore and {} obsidian.",
+                rng.range(2..=4),
+                rng.range(2..=4),
+                rng.range(2..=4),
+                rng.range(5..=20),
+                rng.range(2..=4),
+                rng.range(7..=20),
+            )
+        })
+        .collect();
+    lines.join("\n")
+}
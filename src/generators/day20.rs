@@ -0,0 +1,26 @@
+use super::Rng;
+
+/// Generates `len` numbers to be mixed, exactly one of which is zero. Other values may repeat.
+pub fn numbers(rng: &mut Rng, len: usize) -> Vec<i64> {
+    assert!(len > 1, "need at least one number besides zero");
+    let bound = len as i64 * 3;
+    let mut numbers: Vec<i64> = (0..len)
+        .map(|_| {
+            let n = rng.range(1..=bound);
+            if rng.ratio(1, 2) {
+                -n
+            } else {
+                n
+            }
+        })
+        .collect();
+    let zero = rng.index(0..=len - 1);
+    numbers[zero] = 0;
+    numbers
+}
+
+/// Generates a puzzle input of `len` numbers.
+pub fn input(rng: &mut Rng, len: usize) -> String {
+    let lines: Vec<_> = numbers(rng, len).iter().map(i64::to_string).collect();
+    lines.join("\n")
+}
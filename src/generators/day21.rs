@@ -0,0 +1,136 @@
+use std::collections::HashSet;
+
+use super::Rng;
+
+/// A generated monkey riddle, together with the number `humn` has to yell for `root` to see two equal numbers.
+#[derive(Debug, Clone)]
+pub struct Riddle {
+    pub input: String,
+    pub humn: i64,
+}
+
+struct Builder<'a> {
+    rng: &'a mut Rng,
+    names: HashSet<String>,
+    lines: Vec<String>,
+}
+
+impl Builder<'_> {
+    fn name(&mut self) -> String {
+        loop {
+            let name: String = (0..4)
+                .map(|_| (b'a' + self.rng.below(26) as u8) as char)
+                .collect();
+            if name != "root" && name != "humn" && self.names.insert(name.clone()) {
+                return name;
+            }
+        }
+    }
+
+    fn number(&mut self, value: i64) -> String {
+        let name = self.name();
+        self.lines.push(format!("{name}: {value}"));
+        name
+    }
+
+    fn operation(&mut self, left: &str, op: char, right: &str) -> String {
+        let name = self.name();
+        self.lines.push(format!("{name}: {left} {op} {right}"));
+        name
+    }
+
+    /// Builds a subtree without `humn` that evaluates to a positive number.
+    /// Every operation stays in positive integers, so divisions are always exact.
+    fn constant(&mut self, depth: usize) -> (String, i64) {
+        if depth == 0 || self.rng.ratio(1, 3) {
+            let value = self.rng.range(1..=20);
+            return (self.number(value), value);
+        }
+
+        let (left, a) = self.constant(depth - 1);
+        let (right, b) = self.constant(depth - 1);
+
+        let (op, value) = match self.rng.below(4) {
+            0 => ('+', a + b),
+            1 if a.checked_mul(b).is_some_and(|v| v < 1_000_000) => ('*', a * b),
+            2 if a > b => ('-', a - b),
+            3 if a % b == 0 => ('/', a / b),
+            _ => ('+', a + b),
+        };
+
+        (self.operation(&left, op, &right), value)
+    }
+
+    /// Wraps the branch containing `humn` in an operation with a constant subtree.
+    /// Only operations that `humn` can be solved back through are used.
+    fn wrap(&mut self, branch: &str, value: i64) -> (String, i64) {
+        let humn_left = self.rng.ratio(1, 2);
+
+        if !humn_left && self.rng.ratio(1, 4) {
+            // divide a multiple of the branch by it: `k * value / value = k`.
+            let k = self.rng.range(1..=5);
+            let dividend = self.number(k * value);
+            return (self.operation(&dividend, '/', branch), k);
+        }
+
+        let (constant, c) = self.constant(2);
+
+        let (op, value) = match self.rng.below(4) {
+            1 if value.checked_mul(c).is_some_and(|v| v < 1_000_000_000_000) => ('*', value * c),
+            2 if humn_left && value > c => ('-', value - c),
+            2 if !humn_left && c > value => ('-', c - value),
+            3 if humn_left && value % c == 0 => ('/', value / c),
+            _ => ('+', value + c),
+        };
+
+        let name = if humn_left {
+            self.operation(branch, op, &constant)
+        } else {
+            self.operation(&constant, op, branch)
+        };
+        (name, value)
+    }
+}
+
+/// Generates a riddle where `humn` is nested `depth` operations below `root`.
+pub fn riddle(rng: &mut Rng, depth: usize) -> Riddle {
+    let humn = rng.range(1..=100);
+
+    let mut builder = Builder {
+        rng,
+        names: HashSet::new(),
+        lines: vec![format!("humn: {humn}")],
+    };
+
+    let mut branch = "humn".to_string();
+    let mut value = humn;
+    for _ in 0..depth {
+        (branch, value) = builder.wrap(&branch, value);
+    }
+
+    // the other side of `root` yields the same number without involving `humn`.
+    let offset = builder.rng.range(1..=20);
+    let left = builder.number(value + offset);
+    let right = builder.number(offset);
+    let other = builder.operation(&left, '-', &right);
+
+    let root = if builder.rng.ratio(1, 2) {
+        format!("root: {branch} + {other}")
+    } else {
+        format!("root: {other} + {branch}")
+    };
+    builder.lines.push(root);
+
+    let mut lines = builder.lines;
+    rng.shuffle(&mut lines);
+
+    Riddle {
+        input: lines.join("\n"),
+        humn,
+    }
+}
+
+/// Generates a riddle with roughly `monkeys` monkeys.
+pub fn input(rng: &mut Rng, monkeys: usize) -> String {
+    riddle(rng, (monkeys / 5).max(1)).input
+}
@@ -0,0 +1,31 @@
+use super::Rng;
+
+/// Generates a valley `width` cells wide (and a fifth as high) with blizzards on about half of its cells.
+pub fn input(rng: &mut Rng, width: usize) -> String {
+    let width = width.max(3);
+    let height = (width / 5).max(2);
+
+    let mut lines = vec![format!("#.{}", "#".repeat(width))];
+
+    for _ in 0..height {
+        let row: String = (0..width)
+            .map(|column| {
+                // vertical blizzards in the entry or exit column would leave the valley.
+                let blizzards: &[char] = if column == 0 || column == width - 1 {
+                    &['<', '>']
+                } else {
+                    &['<', '>', '^', 'v']
+                };
+                if rng.ratio(1, 2) {
+                    *rng.choose(blizzards)
+                } else {
+                    '.'
+                }
+            })
+            .collect();
+        lines.push(format!("#{row}#"));
+    }
+
+    lines.push(format!("{}.#", "#".repeat(width)));
+    lines.join("\n")
+}
@@ -0,0 +1,25 @@
+use super::Rng;
+
+const DIGITS: [char; 5] = ['=', '-', '0', '1', '2'];
+
+/// Generates a positive SNAFU number of `digits` digits, without leading zeroes.
+pub fn snafu(rng: &mut Rng, digits: usize) -> String {
+    assert!(digits > 0, "need at least one digit");
+    let mut s = String::with_capacity(digits);
+    s.push(*rng.choose(&['1', '2']));
+    for _ in 1..digits {
+        s.push(*rng.choose(&DIGITS));
+    }
+    s
+}
+
+/// Generates a puzzle input of `len` SNAFU numbers.
+pub fn input(rng: &mut Rng, len: usize) -> String {
+    let lines: Vec<_> = (0..len)
+        .map(|_| {
+            let digits = rng.index(1..=20);
+            snafu(rng, digits)
+        })
+        .collect();
+    lines.join("\n")
+}
@@ -0,0 +1,62 @@
+//! Generators for random puzzle inputs, used by property tests of the solutions and by `cargo gen`.
+//!
+//! Each module produces inputs in the grammar of one day's puzzle.
+use crate::template::Day;
+
+pub mod day07;
+pub mod day11;
+pub mod day13;
+pub mod day15;
+pub mod day16;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day24;
+pub mod day25;
+
+mod rng;
+
+pub use rng::Rng;
+
+/// Days that have an input generator.
+pub const GENERATED_DAYS: [u8; 10] = [7, 11, 13, 15, 16, 19, 20, 21, 24, 25];
+
+/// Generates a puzzle input for a day. What `size` counts depends on the puzzle, e.g. directories for
+/// day 7, sensors for day 15, valves for day 16 or the valley width for day 24. Returns `None` for days without a generator.
+pub fn generate(day: Day, size: usize, seed: u64) -> Option<String> {
+    let mut rng = Rng::new(seed);
+    let rng = &mut rng;
+
+    let input = match day.into_inner() {
+        7 => day07::input(rng, size),
+        11 => day11::input(rng, size),
+        13 => day13::input(rng, size, 4),
+        15 => day15::input(rng, size),
+        16 => day16::input(rng, size),
+        19 => day19::input(rng, size),
+        20 => day20::input(rng, size.max(2)),
+        21 => day21::input(rng, size),
+        24 => day24::input(rng, size),
+        25 => day25::input(rng, size),
+        _ => return None,
+    };
+
+    Some(input)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{generate, GENERATED_DAYS};
+    use crate::template::Day;
+
+    #[test]
+    fn generates_inputs_for_generated_days() {
+        for day in GENERATED_DAYS {
+            let day = Day::new(day).unwrap();
+            let input = generate(day, 10, 1).unwrap();
+            assert!(!input.trim().is_empty(), "empty input for day {day}");
+            assert_eq!(generate(day, 10, 1), Some(input));
+        }
+        assert_eq!(generate(Day::new(1).unwrap(), 10, 1), None);
+    }
+}
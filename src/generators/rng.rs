@@ -0,0 +1,83 @@
+use std::ops::RangeInclusive;
+
+/// A small, seedable pseudo random number generator (SplitMix64).
+///
+/// Not suitable for anything but generating puzzle inputs, but it is deterministic across platforms
+/// which makes failing cases reproducible from their seed.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a value in `0..n`.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "range must not be empty");
+        self.next_u64() % n
+    }
+
+    /// Returns a value in the given inclusive range.
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "range must not be empty");
+        let width = end.abs_diff(start) + 1;
+        start.wrapping_add_unsigned(self.below(width))
+    }
+
+    /// Returns a `usize` in the given inclusive range.
+    pub fn index(&mut self, range: RangeInclusive<usize>) -> usize {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "range must not be empty");
+        start + self.below((end - start + 1) as u64) as usize
+    }
+
+    /// Returns `true` with a probability of `numerator / denominator`.
+    pub fn ratio(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.below(denominator) < numerator
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(0..=items.len() - 1)]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.index(0..=i));
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Rng;
+
+    #[test]
+    fn is_deterministic() {
+        let a: Vec<_> = (0..8).map(|_| Rng::new(42).next_u64()).collect();
+        assert!(a.iter().all(|&x| x == a[0]));
+        let mut rng = Rng::new(42);
+        assert_ne!(rng.next_u64(), rng.next_u64());
+    }
+
+    #[test]
+    fn stays_in_range() {
+        let mut rng = Rng::new(7);
+        for _ in 0..1000 {
+            assert!((-3..=3).contains(&rng.range(-3..=3)));
+            assert!((2..=4).contains(&rng.index(2..=4)));
+        }
+        assert_eq!(rng.range(i64::MIN..=i64::MIN), i64::MIN);
+    }
+}
@@ -1,3 +1,7 @@
 pub mod template;
 
+pub mod framebuffer;
+pub mod generators;
+pub mod property;
+
 // Use this file to add helper functions and additional modules.
@@ -1,42 +1,71 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, example, generate, read, readme, scaffold, solve, time,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
-#[cfg(feature = "today")]
-use std::process;
+use advent_of_code::template::commands::today;
 
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::{
+        commands::time::{parse_sizes, Format},
+        Day, DaySet,
+    };
     use std::process;
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            days: DaySet,
         },
         Read {
-            day: Day,
+            days: DaySet,
+        },
+        Readme {
+            dry_run: bool,
+            check: bool,
         },
         Scaffold {
-            day: Day,
+            days: DaySet,
             download: bool,
         },
+        Example {
+            day: Day,
+            part: u8,
+            expect: String,
+        },
+        Generate {
+            day: Day,
+            size: usize,
+            seed: Option<u64>,
+        },
         Solve {
             day: Day,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            watch: bool,
+            input: Option<String>,
+            allocs: bool,
         },
         All {
+            days: Option<DaySet>,
+            tag: Option<String>,
             release: bool,
+            record: bool,
         },
         Time {
             all: bool,
-            day: Option<Day>,
+            days: Option<DaySet>,
+            tag: Option<String>,
             store: bool,
+            sizes: Option<Vec<usize>>,
+            allocs: bool,
+            format: Option<Format>,
         },
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            wait: bool,
+        },
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
@@ -45,35 +74,73 @@ mod args {
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
+                record: args.contains("--record"),
+                tag: args.opt_value_from_str("--tag")?,
+                days: args.opt_free_from_str()?,
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let allocs = args.contains("--allocs");
 
                 AppArguments::Time {
                     all,
-                    day: args.opt_free_from_str()?,
+                    sizes: args.opt_value_from_fn("--sizes", parse_sizes)?,
+                    format: args.opt_value_from_str("--format")?,
+                    tag: args.opt_value_from_str("--tag")?,
+                    days: args.opt_free_from_str()?,
                     store,
+                    allocs,
                 }
             }
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                days: args.free_from_str()?,
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                days: args.free_from_str()?,
+            },
+            Some("readme") => AppArguments::Readme {
+                dry_run: args.contains("--dry-run"),
+                check: args.contains("--check"),
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                days: args.free_from_str()?,
                 download: args.contains("--download"),
             },
+            Some("example") => {
+                let part = args.value_from_str("--part")?;
+                let expect = args.value_from_str("--expect")?;
+
+                match args.free_from_str::<String>()?.as_str() {
+                    "add" => AppArguments::Example {
+                        day: args.free_from_str()?,
+                        part,
+                        expect,
+                    },
+                    x => {
+                        eprintln!("Unknown example command: {x}");
+                        process::exit(1);
+                    }
+                }
+            }
+            Some("gen") => AppArguments::Generate {
+                size: args.value_from_str("--size")?,
+                seed: args.opt_value_from_str("--seed")?,
+                day: args.free_from_str()?,
+            },
             Some("solve") => AppArguments::Solve {
+                input: args.opt_value_from_str("--input")?,
                 day: args.free_from_str()?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                watch: args.contains("--watch"),
+                allocs: args.contains("--allocs"),
             },
             #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
+            Some("today") => AppArguments::Today {
+                wait: args.contains("--wait"),
+            },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -100,39 +167,45 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day, download } => {
-                scaffold::handle(day);
-                if download {
-                    download::handle(day);
+            AppArguments::All {
+                days,
+                tag,
+                release,
+                record,
+            } => all::handle(days, tag.as_deref(), release, record),
+            AppArguments::Time {
+                days,
+                tag,
+                all,
+                store,
+                sizes,
+                allocs,
+                format,
+            } => time::handle(days, tag.as_deref(), all, store, sizes, allocs, format),
+            AppArguments::Download { days } => days.into_iter().for_each(download::handle),
+            AppArguments::Read { days } => days.into_iter().for_each(read::handle),
+            AppArguments::Readme { dry_run, check } => readme::handle(dry_run, check),
+            AppArguments::Scaffold { days, download } => {
+                for day in days {
+                    scaffold::handle(day);
+                    if download {
+                        download::handle(day);
+                    }
                 }
             }
+            AppArguments::Example { day, part, expect } => example::handle(day, part, &expect),
+            AppArguments::Generate { day, size, seed } => generate::handle(day, size, seed),
             AppArguments::Solve {
                 day,
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                watch,
+                input,
+                allocs,
+            } => solve::handle(day, release, dhat, allocs, submit, watch, input.as_deref()),
             #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
-            }
+            AppArguments::Today { wait } => today::handle(wait),
         },
     };
 }
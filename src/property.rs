@@ -0,0 +1,36 @@
+//! A minimal property testing harness, built on the input [generators](crate::generators).
+//!
+//! Every case gets its own seed, derived from a fixed base seed so test runs are reproducible.
+//! The base seed and number of cases can be overridden with the `AOC_PROPERTY_SEED` and
+//! `AOC_PROPERTY_CASES` environment variables, e.g. to replay a failing case.
+use std::{
+    env,
+    panic::{self, AssertUnwindSafe},
+};
+
+use crate::generators::Rng;
+
+const DEFAULT_SEED: u64 = 2022;
+
+fn env_var<T: std::str::FromStr>(name: &str) -> Option<T> {
+    env::var(name).ok().and_then(|v| v.parse().ok())
+}
+
+/// Runs `property` against `cases` random generators. Panics (with the seed of the failing case)
+/// if the property panics for any of them.
+pub fn check(cases: usize, property: impl Fn(&mut Rng)) {
+    let base_seed = env_var("AOC_PROPERTY_SEED").unwrap_or(DEFAULT_SEED);
+    let cases = env_var("AOC_PROPERTY_CASES").unwrap_or(cases);
+
+    for seed in (0..cases as u64).map(|i| base_seed.wrapping_add(i)) {
+        let mut rng = Rng::new(seed);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| property(&mut rng)));
+
+        if let Err(e) = result {
+            eprintln!(
+                "property failed for seed {seed}, replay with `AOC_PROPERTY_SEED={seed} AOC_PROPERTY_CASES=1`."
+            );
+            panic::resume_unwind(e);
+        }
+    }
+}
@@ -0,0 +1,163 @@
+/// A global allocator that counts allocations, used when the `count-allocs` feature is enabled.
+/// Solutions install it through the `solution!` macro, the runner reads it around each part.
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fmt::Display,
+    str::FromStr,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+static CURRENT: AtomicU64 = AtomicU64::new(0);
+static PEAK: AtomicU64 = AtomicU64::new(0);
+static BASELINE: AtomicU64 = AtomicU64::new(0);
+
+/// Heap usage of a single solution part.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Number of allocations, including reallocations.
+    pub allocations: u64,
+    /// Total bytes requested over all allocations.
+    pub bytes: u64,
+    /// Highest amount of live heap memory above what was allocated before the part started.
+    pub peak: u64,
+}
+
+pub struct CountingAlloc;
+
+fn record_alloc(size: u64) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    BYTES.fetch_add(size, Ordering::Relaxed);
+    let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(current, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size() as u64);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size() as u64);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT.fetch_sub(layout.size() as u64, Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            CURRENT.fetch_sub(layout.size() as u64, Ordering::Relaxed);
+            record_alloc(new_size as u64);
+        }
+        new_ptr
+    }
+}
+
+/// Starts a new measurement. Memory that is live at this point does not count towards the peak.
+pub fn reset() {
+    let current = CURRENT.load(Ordering::Relaxed);
+    ALLOCATIONS.store(0, Ordering::Relaxed);
+    BYTES.store(0, Ordering::Relaxed);
+    BASELINE.store(current, Ordering::Relaxed);
+    PEAK.store(current, Ordering::Relaxed);
+}
+
+/// Returns the heap usage since the last call to [`reset`].
+pub fn stats() -> AllocStats {
+    AllocStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+        bytes: BYTES.load(Ordering::Relaxed),
+        peak: PEAK
+            .load(Ordering::Relaxed)
+            .saturating_sub(BASELINE.load(Ordering::Relaxed)),
+    }
+}
+
+/// Formats a byte count with a binary unit, e.g. `1.5 KiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64 / 1024_f64;
+    let mut unit = 0;
+    while value >= 1024_f64 && unit < UNITS.len() - 1 {
+        value /= 1024_f64;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/// Exact format that is printed by the runner and parsed back by `cargo time`.
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocs, {} B, peak {} B",
+            self.allocations, self.bytes, self.peak
+        )
+    }
+}
+
+impl FromStr for AllocStats {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || format!("\"{s}\" is not a valid allocation summary.");
+
+        let mut parts = s.split(", ");
+        let mut field = |prefix: &str, suffix: &str| {
+            parts
+                .next()
+                .and_then(|p| p.strip_prefix(prefix))
+                .and_then(|p| p.strip_suffix(suffix))
+                .and_then(|p| p.parse::<u64>().ok())
+                .ok_or_else(err)
+        };
+
+        Ok(AllocStats {
+            allocations: field("", " allocs")?,
+            bytes: field("", " B")?,
+            peak: field("peak ", " B")?,
+        })
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bytes, AllocStats};
+
+    #[test]
+    fn roundtrips_stats() {
+        let stats = AllocStats {
+            allocations: 12,
+            bytes: 4096,
+            peak: 800,
+        };
+        assert_eq!(stats.to_string(), "12 allocs, 4096 B, peak 800 B");
+        assert_eq!(stats.to_string().parse(), Ok(stats));
+        assert!("12 allocs, 4096 B".parse::<AllocStats>().is_err());
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(800), "800 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+}
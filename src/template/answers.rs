@@ -0,0 +1,284 @@
+use std::{
+    collections::HashMap,
+    io::{Error, ErrorKind},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    manifest::Manifest,
+    run_multi::{child_commands, RunOptions},
+    Day,
+};
+
+/// Represents the answers a solution gave for a single day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Answer {
+    pub day: Day,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+/// Represents recorded answers for a set of days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+impl Answer {
+    /// Returns the answer for a part, if one was given.
+    pub fn part(&self, part: u8) -> Option<&String> {
+        match part {
+            1 => self.part_1.as_ref(),
+            2 => self.part_2.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+impl Answers {
+    /// Records the answers in the project manifest.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let mut manifest =
+            Manifest::read_from_file().map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+        manifest.set_answers(self);
+        manifest.store_file()
+    }
+
+    /// Reads the answers recorded in the project manifest.
+    pub fn read_from_file() -> Result<Self, String> {
+        Manifest::read_from_file().map(|manifest| manifest.answers())
+    }
+
+    /// Merge two sets of answers. Parts answered in `new` overwrite those in `self`, parts that
+    /// `new` has no answer for keep their recorded answer.
+    pub fn merge(&self, new: &Self) -> Self {
+        let mut data: Vec<Answer> = self.data.clone();
+
+        for answer in &new.data {
+            match data.iter_mut().find(|a| a.day == answer.day) {
+                Some(old) => {
+                    old.part_1 = answer.part_1.clone().or(old.part_1.take());
+                    old.part_2 = answer.part_2.clone().or(old.part_2.take());
+                }
+                None => data.push(answer.clone()),
+            }
+        }
+
+        data.sort_unstable_by_key(|a| a.day);
+        Answers { data }
+    }
+
+    /// Returns the recorded answer for a day.
+    pub fn get(&self, day: Day) -> Option<&Answer> {
+        self.data.iter().find(|a| a.day == day)
+    }
+}
+
+/// Runs the solution for a day against its real input, using the same runner as `cargo solve`,
+/// and collects the answers it prints.
+pub fn run_day(day: Day, is_release: bool) -> Option<Answer> {
+    let output = child_commands::run_solution(
+        day,
+        &RunOptions {
+            is_release,
+            ..RunOptions::default()
+        },
+        None,
+    )
+    .ok()?;
+
+    if output.is_empty() {
+        None
+    } else {
+        Some(child_commands::parse_answers(&output, day))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+
+        map.insert(
+            "part_1".into(),
+            value
+                .part_1
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        map.insert(
+            "part_2".into(),
+            value
+                .part_2
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        let part_1 = json
+            .get("part_1")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_1 to be null or string.")?;
+
+        let part_2 = json
+            .get("part_2")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_2 to be null or string.")?;
+
+        Ok(Answer {
+            day,
+            part_1: part_1.cloned(),
+            part_2: part_2.cloned(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::day;
+
+    use super::{Answer, Answers};
+
+    fn get_mock_answers() -> Answers {
+        Answers {
+            data: vec![
+                Answer {
+                    day: day!(1),
+                    part_1: Some("24000".into()),
+                    part_2: Some("45000".into()),
+                },
+                Answer {
+                    day: day!(10),
+                    part_1: Some("13140".into()),
+                    part_2: Some("##..##\n###...".into()),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let answers = get_mock_answers();
+        let json = tinyjson::JsonValue::from(answers.clone())
+            .stringify()
+            .unwrap();
+        let parsed = Answers::try_from(json).unwrap();
+        assert_eq!(parsed.data, answers.data);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_answers() {
+        let json = r#"{ "data": [{ "day": "26", "part_1": null, "part_2": null }] }"#.to_string();
+        Answers::try_from(json).unwrap();
+    }
+
+    #[test]
+    fn merges_answers() {
+        let answers = get_mock_answers();
+        let other = Answers {
+            data: vec![Answer {
+                day: day!(1),
+                part_1: Some("1".into()),
+                part_2: None,
+            }],
+        };
+        let merged = answers.merge(&other);
+        assert_eq!(merged.data.len(), 2);
+        assert_eq!(merged.get(day!(1)).unwrap().part(1), Some(&"1".to_string()));
+        assert_eq!(
+            merged.get(day!(10)).unwrap().part(1),
+            Some(&"13140".to_string())
+        );
+    }
+
+    #[test]
+    fn keeps_recorded_parts_without_new_answer() {
+        let answers = get_mock_answers();
+        let other = Answers {
+            data: vec![
+                Answer {
+                    day: day!(1),
+                    part_1: Some("1".into()),
+                    part_2: None,
+                },
+                Answer {
+                    day: day!(2),
+                    part_1: Some("15".into()),
+                    part_2: None,
+                },
+            ],
+        };
+        let merged = answers.merge(&other);
+        assert_eq!(merged.data.len(), 3);
+        assert_eq!(merged.get(day!(1)).unwrap().part(1), Some(&"1".to_string()));
+        assert_eq!(
+            merged.get(day!(1)).unwrap().part(2),
+            Some(&"45000".to_string())
+        );
+        assert_eq!(merged.get(day!(2)).unwrap().part(2), None);
+    }
+}
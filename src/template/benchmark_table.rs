@@ -0,0 +1,440 @@
+/// Renders benchmark timings as a table, either as markdown for the readme or for the terminal.
+use std::{env, time::Duration};
+
+use crate::template::alloc_counter::{format_bytes, AllocStats};
+use crate::template::manifest::Manifest;
+use crate::template::run_multi::child_commands::parse_duration;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+const ANSI_RED: &str = "\x1b[31m";
+
+/// Width of the cost bar of the most expensive day, in characters.
+const BAR_WIDTH: usize = 20;
+const BAR_EIGHTHS: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+/// Controls how the table is ordered and which days are marked.
+/// Read from `AOC_BENCHMARK_SORT` (`day` or `cost`) and `AOC_BENCHMARK_BUDGET` (e.g. `1ms`).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TableOptions {
+    pub sort_by_cost: bool,
+    /// Days whose total run time exceeds this many nanoseconds are marked.
+    pub budget_nanos: Option<f64>,
+}
+
+impl TableOptions {
+    pub fn from_env() -> Self {
+        let sort_by_cost = env::var("AOC_BENCHMARK_SORT").is_ok_and(|s| s.trim() == "cost");
+
+        let budget_nanos = env::var("AOC_BENCHMARK_BUDGET")
+            .ok()
+            .filter(|s| !s.trim().is_empty())
+            .and_then(|s| {
+                let budget = parse_duration(s.trim());
+                if budget.is_none() {
+                    eprintln!(
+                        "Ignoring invalid AOC_BENCHMARK_BUDGET \"{s}\", expected e.g. \"1ms\"."
+                    );
+                }
+                budget
+            });
+
+        TableOptions {
+            sort_by_cost,
+            budget_nanos,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Column {
+    Day,
+    Parse,
+    Part1,
+    Part2,
+    ParseHeap,
+    Part1Heap,
+    Part2Heap,
+    Share,
+    Cost,
+}
+
+impl Column {
+    fn title(self) -> &'static str {
+        match self {
+            Column::Day => "Day",
+            Column::Parse => "Parse",
+            Column::Part1 => "Part 1",
+            Column::Part2 => "Part 2",
+            Column::ParseHeap => "Parse heap",
+            Column::Part1Heap => "Part 1 heap",
+            Column::Part2Heap => "Part 2 heap",
+            Column::Share => "Share",
+            Column::Cost => "Cost",
+        }
+    }
+}
+
+/// Output target of a rendered cell. Markdown wraps values in code spans and links days to their solution.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Target<'a> {
+    /// Days are linked to the solution the manifest lists for them.
+    Markdown(&'a Manifest),
+    Terminal,
+}
+
+pub struct BenchmarkTable {
+    columns: Vec<Column>,
+    rows: Vec<Timing>,
+    total_nanos: f64,
+    max_nanos: f64,
+    options: TableOptions,
+}
+
+impl BenchmarkTable {
+    pub fn new(timings: &Timings, options: &TableOptions) -> Self {
+        // the parse column is only shown for solutions that declare a `parse` function,
+        // heap columns only once allocations were counted with `cargo time --allocs`.
+        let has_parse = timings.data.iter().any(|t| t.parse.is_some());
+        let has_allocs = timings.data.iter().any(|t| {
+            t.parse_allocs.is_some() || t.part_1_allocs.is_some() || t.part_2_allocs.is_some()
+        });
+
+        let mut columns = vec![Column::Day];
+        if has_parse {
+            columns.push(Column::Parse);
+        }
+        columns.extend([Column::Part1, Column::Part2]);
+        if has_allocs {
+            if has_parse {
+                columns.push(Column::ParseHeap);
+            }
+            columns.extend([Column::Part1Heap, Column::Part2Heap]);
+        }
+        columns.extend([Column::Share, Column::Cost]);
+
+        let mut rows = timings.data.clone();
+        if options.sort_by_cost {
+            rows.sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos));
+        }
+
+        BenchmarkTable {
+            columns,
+            total_nanos: rows.iter().map(|t| t.total_nanos).sum(),
+            max_nanos: rows.iter().map(|t| t.total_nanos).fold(0_f64, f64::max),
+            rows,
+            options: options.clone(),
+        }
+    }
+
+    fn is_over_budget(&self, timing: &Timing) -> bool {
+        self.options
+            .budget_nanos
+            .is_some_and(|budget| timing.total_nanos > budget)
+    }
+
+    fn cell(&self, timing: &Timing, column: Column, target: Target) -> String {
+        let time = |time: &Option<String>| {
+            let time = time.clone().unwrap_or_else(|| "-".into());
+            match target {
+                Target::Markdown(_) => format!("`{time}`"),
+                Target::Terminal => time,
+            }
+        };
+
+        match column {
+            Column::Day => {
+                let day = timing.day;
+                match (target, self.is_over_budget(timing)) {
+                    (Target::Markdown(manifest), over_budget) => {
+                        let name = format!("Day {}", day.into_inner());
+                        let name = match manifest.get(day) {
+                            Some(entry) => format!("[{name}]({})", entry.link()),
+                            None => name,
+                        };
+                        if over_budget {
+                            format!("{name} ⚠️")
+                        } else {
+                            name
+                        }
+                    }
+                    (Target::Terminal, false) => format!("Day {day}"),
+                    (Target::Terminal, true) => format!("Day {day}*"),
+                }
+            }
+            Column::Parse => time(&timing.parse),
+            Column::Part1 => time(&timing.part_1),
+            Column::Part2 => time(&timing.part_2),
+            Column::ParseHeap => format_allocs(timing.parse_allocs, target),
+            Column::Part1Heap => format_allocs(timing.part_1_allocs, target),
+            Column::Part2Heap => format_allocs(timing.part_2_allocs, target),
+            Column::Share => {
+                let share = if self.total_nanos > 0_f64 {
+                    timing.total_nanos / self.total_nanos * 100_f64
+                } else {
+                    0_f64
+                };
+                format!("{share:.1}%")
+            }
+            Column::Cost => {
+                let bar = bar(timing.total_nanos, self.max_nanos);
+                match target {
+                    Target::Markdown(_) if bar.is_empty() => String::new(),
+                    Target::Markdown(_) => format!("`{bar}`"),
+                    Target::Terminal => bar,
+                }
+            }
+        }
+    }
+
+    fn budget_note(&self, target: Target) -> Option<String> {
+        let budget = self.options.budget_nanos?;
+        if !self.rows.iter().any(|t| self.is_over_budget(t)) {
+            return None;
+        }
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let budget = Duration::from_nanos(budget as u64);
+        Some(match target {
+            Target::Markdown(_) => format!("_⚠️ over the budget of {budget:?} per day._"),
+            Target::Terminal => format!("* over the budget of {budget:?} per day."),
+        })
+    }
+
+    /// Renders the table and the total as markdown lines, as shown in the readme.
+    /// Days link to their solution as listed in the manifest.
+    pub fn to_markdown(&self, total_millis: f64, manifest: &Manifest) -> Vec<String> {
+        let titles: Vec<_> = self.columns.iter().map(|c| c.title()).collect();
+
+        let mut lines = vec![
+            format!("| {} |", titles.join(" | ")),
+            format!("|{}", " :---: |".repeat(self.columns.len())),
+        ];
+
+        for timing in &self.rows {
+            let cells: Vec<_> = self
+                .columns
+                .iter()
+                .map(|&c| self.cell(timing, c, Target::Markdown(manifest)))
+                .collect();
+            let row: String = cells
+                .iter()
+                .map(|c| {
+                    if c.is_empty() {
+                        " |".into()
+                    } else {
+                        format!(" {c} |")
+                    }
+                })
+                .collect();
+            lines.push(format!("|{row}"));
+        }
+
+        lines.push(String::new());
+        lines.push(format!("**Total: {total_millis:.2}ms**"));
+        lines.extend(self.budget_note(Target::Markdown(manifest)));
+        lines
+    }
+
+    /// Renders the table with aligned columns for the terminal. Days over budget are highlighted.
+    pub fn to_terminal(&self, total_millis: f64) -> String {
+        let rows: Vec<Vec<String>> = self
+            .rows
+            .iter()
+            .map(|timing| {
+                self.columns
+                    .iter()
+                    .map(|&c| self.cell(timing, c, Target::Terminal))
+                    .collect()
+            })
+            .collect();
+
+        let widths: Vec<usize> = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(i, c)| {
+                rows.iter()
+                    .map(|r| r[i].chars().count())
+                    .chain([c.title().len()])
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        let format_row = |cells: &[String]| {
+            let padded: Vec<String> = cells
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{cell:<width$}"))
+                .collect();
+            padded.join("  ").trim_end().to_string()
+        };
+
+        let titles: Vec<String> = self.columns.iter().map(|c| c.title().into()).collect();
+        let mut lines = vec![format!("{ANSI_BOLD}{}{ANSI_RESET}", format_row(&titles))];
+
+        for (timing, cells) in self.rows.iter().zip(&rows) {
+            let row = format_row(cells);
+            if self.is_over_budget(timing) {
+                lines.push(format!("{ANSI_RED}{row}{ANSI_RESET}"));
+            } else {
+                lines.push(row);
+            }
+        }
+
+        lines.push(String::new());
+        lines.push(format!(
+            "{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+        ));
+        lines.extend(self.budget_note(Target::Terminal));
+        lines.join("\n")
+    }
+}
+
+fn format_allocs(allocs: Option<AllocStats>, target: Target) -> String {
+    let Some(a) = allocs else {
+        return "-".into();
+    };
+
+    let (bytes, peak) = (format_bytes(a.bytes), format_bytes(a.peak));
+    match target {
+        Target::Markdown(_) => format!("`{}` allocs, `{bytes}`, peak `{peak}`", a.allocations),
+        Target::Terminal => format!("{} allocs, {bytes}, peak {peak}", a.allocations),
+    }
+}
+
+/// Draws a horizontal bar proportional to `value / max`, with a resolution of an eighth character.
+fn bar(value: f64, max: f64) -> String {
+    if max <= 0_f64 {
+        return String::new();
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let eighths = (value / max * (BAR_WIDTH * 8) as f64).round() as usize;
+
+    let mut bar = "█".repeat(eighths / 8);
+    let rest = eighths % 8;
+    if rest > 0 {
+        bar.push(BAR_EIGHTHS[rest]);
+    }
+    bar
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{bar, BenchmarkTable, TableOptions};
+    use crate::template::{
+        manifest::Manifest,
+        timings::{Timing, Timings},
+        Day,
+    };
+
+    fn manifest() -> Manifest {
+        let mut manifest = Manifest::default();
+        manifest.register(Day::new(1).unwrap());
+        manifest.register(Day::new(2).unwrap());
+        manifest
+    }
+
+    fn timing(day: u8, total_nanos: f64) -> Timing {
+        Timing {
+            day: Day::new(day).unwrap(),
+            parse: None,
+            part_1: Some("1ms".into()),
+            part_2: Some("2ms".into()),
+            total_nanos,
+            parse_allocs: None,
+            part_1_allocs: None,
+            part_2_allocs: None,
+        }
+    }
+
+    fn mock_timings() -> Timings {
+        Timings {
+            data: vec![timing(1, 1e6), timing(2, 3e6)],
+        }
+    }
+
+    #[test]
+    fn draws_bars() {
+        assert_eq!(bar(1.0, 1.0), "█".repeat(20));
+        assert_eq!(bar(0.5, 1.0), "█".repeat(10));
+        assert_eq!(bar(1.0, 3.0), "██████▋");
+        assert_eq!(bar(0.0, 1.0), "");
+        assert_eq!(bar(1.0, 0.0), "");
+    }
+
+    #[test]
+    fn renders_shares() {
+        let table = BenchmarkTable::new(&mock_timings(), &TableOptions::default());
+        let lines = table.to_markdown(4.0, &manifest());
+        assert_eq!(lines[0], "| Day | Part 1 | Part 2 | Share | Cost |");
+        assert_eq!(
+            lines[2],
+            "| [Day 1](./src/bin/01.rs) | `1ms` | `2ms` | 25.0% | `██████▋` |"
+        );
+        assert_eq!(
+            lines[3],
+            format!(
+                "| [Day 2](./src/bin/02.rs) | `1ms` | `2ms` | 75.0% | `{}` |",
+                "█".repeat(20)
+            )
+        );
+    }
+
+    #[test]
+    fn links_days_through_the_manifest() {
+        let mut manifest = Manifest::default();
+        manifest.register(Day::new(1).unwrap()).bin = "src/bin/day01.rs".into();
+        let table = BenchmarkTable::new(&mock_timings(), &TableOptions::default());
+        let lines = table.to_markdown(4.0, &manifest);
+        assert!(lines[2].starts_with("| [Day 1](./src/bin/day01.rs) |"));
+        assert!(lines[3].starts_with("| Day 2 |"));
+    }
+
+    #[test]
+    fn sorts_by_cost() {
+        let options = TableOptions {
+            sort_by_cost: true,
+            budget_nanos: None,
+        };
+        let table = BenchmarkTable::new(&mock_timings(), &options);
+        let lines = table.to_markdown(4.0, &manifest());
+        assert!(lines[2].starts_with("| [Day 2]"));
+        assert!(lines[3].starts_with("| [Day 1]"));
+    }
+
+    #[test]
+    fn marks_days_over_budget() {
+        let options = TableOptions {
+            sort_by_cost: false,
+            budget_nanos: Some(2e6),
+        };
+        let table = BenchmarkTable::new(&mock_timings(), &options);
+
+        let lines = table.to_markdown(4.0, &manifest());
+        assert!(lines[2].starts_with("| [Day 1](./src/bin/01.rs) |"));
+        assert!(lines[3].starts_with("| [Day 2](./src/bin/02.rs) ⚠️ |"));
+        assert_eq!(
+            lines.last().unwrap(),
+            "_⚠️ over the budget of 2ms per day._"
+        );
+
+        let terminal = table.to_terminal(4.0);
+        assert!(terminal.contains("\x1b[31mDay 02*"));
+        assert!(terminal.ends_with("* over the budget of 2ms per day."));
+    }
+
+    #[test]
+    fn renders_terminal_table() {
+        let table = BenchmarkTable::new(&mock_timings(), &TableOptions::default());
+        let terminal = table.to_terminal(4.0);
+        let lines: Vec<&str> = terminal.lines().collect();
+        assert_eq!(
+            lines[0],
+            "\x1b[1mDay     Part 1  Part 2  Share  Cost\x1b[0m"
+        );
+        assert_eq!(lines[1], "Day 01  1ms     2ms     25.0%  ██████▋");
+    }
+}
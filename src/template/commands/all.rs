@@ -1,5 +1,48 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::process;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::{
+    answers::Answers,
+    manifest::Manifest,
+    run_multi::{filter_by_tag, run_multi, RunOptions},
+    DaySet,
+};
+
+/// Runs the given days, or every day in the project manifest, optionally only the ones with a tag.
+pub fn handle(days: Option<DaySet>, tag: Option<&str>, is_release: bool, is_record: bool) {
+    let manifest = Manifest::read_from_file().unwrap_or_else(|e| {
+        eprintln!("Failed to read the project manifest: {e}");
+        process::exit(1);
+    });
+    let mut days = days.unwrap_or_else(|| manifest.days());
+
+    if let Some(tag) = tag {
+        days = filter_by_tag(days, tag, &manifest).unwrap_or_else(|e| {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        });
+    }
+
+    let options = RunOptions {
+        is_release,
+        ..RunOptions::default()
+    };
+    let (_, answers) = run_multi(&days, &manifest, &options);
+
+    if is_record {
+        let recorded = Answers::read_from_file().unwrap_or_else(|e| {
+            eprintln!("Failed to read the recorded answers, not storing the new ones: {e}");
+            process::exit(1);
+        });
+        let merged_answers = recorded.merge(&answers);
+
+        println!();
+        match merged_answers.store_file() {
+            Ok(()) => {
+                println!("Stored answers for {} days.", answers.data.len());
+            }
+            Err(e) => {
+                eprintln!("Failed to store answers: {e}");
+            }
+        }
+    }
 }
@@ -0,0 +1,246 @@
+use std::{
+    fs,
+    io::{self, Read},
+    path::Path,
+    process,
+};
+
+use crate::template::{manifest::Manifest, Day};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Error {
+    InvalidPart(u8),
+    TableNotFound(u8),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::InvalidPart(part) => write!(f, "expecting part 1 or 2, got {part}."),
+            Error::TableNotFound(part) => write!(
+                f,
+                "could not find an `rstest` table for `test_part_{}`.",
+                part_name(*part)
+            ),
+        }
+    }
+}
+
+pub fn handle(day: Day, part: u8, expected: &str) {
+    let mut manifest = Manifest::read_from_file().unwrap_or_else(|e| {
+        eprintln!("Failed to read the project manifest: {e}");
+        process::exit(1);
+    });
+    let Some(entry) = manifest.get(day) else {
+        eprintln!("Day {day} has not been scaffolded yet. Run `cargo scaffold {day}` first.");
+        process::exit(1);
+    };
+    let module_path = entry.bin.clone();
+
+    let mut input = String::new();
+    if let Err(e) = io::stdin().read_to_string(&mut input) {
+        eprintln!("Failed to read example from stdin: {e}");
+        process::exit(1);
+    }
+
+    let source = match fs::read_to_string(&module_path) {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("Failed to read module file \"{module_path}\": {e}");
+            process::exit(1);
+        }
+    };
+
+    let (example_path, input_expr) = next_example_file(day);
+
+    let name = case_name(&example_path);
+    let case = format!(
+        "#[case::{name}({input_expr}, {})]",
+        expected_literal(expected)
+    );
+
+    let source = match add_case(&source, part, &name, &case) {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("Failed to add example to \"{module_path}\": {e}");
+            process::exit(1);
+        }
+    };
+
+    if let Err(e) = fs::write(&example_path, input) {
+        eprintln!("Failed to write example file: {e}");
+        process::exit(1);
+    }
+    println!("Created example file \"{example_path}\"");
+
+    manifest.add_example(day, &example_path);
+    if let Err(e) = manifest.store_file() {
+        eprintln!("Failed to update the project manifest: {e}");
+        process::exit(1);
+    }
+
+    if let Err(e) = fs::write(&module_path, source) {
+        eprintln!("Failed to write module file: {e}");
+        process::exit(1);
+    }
+    println!("Added test case to \"{module_path}\"");
+
+    println!("---");
+    println!("🎄 Type `cargo test --bin {day}` to run your tests.");
+}
+
+/// Picks the file a new example is written to, and the expression that reads it in a test.
+/// The plain example file is used while it is empty, otherwise the next free part suffix (e.g. `01-2.txt`).
+fn next_example_file(day: Day) -> (String, String) {
+    let default_path = format!("data/examples/{day}.txt");
+    if fs::read_to_string(&default_path).map_or(true, |s| s.is_empty()) {
+        return (
+            default_path,
+            "advent_of_code::template::read_file(\"examples\", DAY)".into(),
+        );
+    }
+
+    let n = (2..)
+        .find(|n| !Path::new(&format!("data/examples/{day}-{n}.txt")).exists())
+        .unwrap();
+
+    (
+        format!("data/examples/{day}-{n}.txt"),
+        format!("advent_of_code::template::read_file_part(\"examples\", DAY, {n})"),
+    )
+}
+
+fn case_name(example_path: &str) -> String {
+    let stem = Path::new(example_path)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default();
+
+    match stem.split_once('-') {
+        Some((_, n)) => format!("example_{n}"),
+        None => "example".into(),
+    }
+}
+
+/// Numbers are used as-is, anything else is treated as a string answer.
+fn expected_literal(expected: &str) -> String {
+    if expected.parse::<i128>().is_ok() {
+        format!("Some({expected})")
+    } else {
+        format!("Some({expected:?}.to_string())")
+    }
+}
+
+fn part_name(part: u8) -> &'static str {
+    match part {
+        1 => "one",
+        _ => "two",
+    }
+}
+
+/// Adds a `#[case]` attribute to the `rstest` table of a part.
+/// A case with the same name is replaced, otherwise the case is appended after the existing ones.
+fn add_case(source: &str, part: u8, name: &str, case: &str) -> Result<String, Error> {
+    if part != 1 && part != 2 {
+        return Err(Error::InvalidPart(part));
+    }
+
+    let signature = format!("fn test_part_{}(", part_name(part));
+    let mut lines: Vec<String> = source.lines().map(ToString::to_string).collect();
+
+    let fn_index = lines
+        .iter()
+        .position(|l| l.trim_start().starts_with(&signature))
+        .ok_or(Error::TableNotFound(part))?;
+
+    let table_start = lines[..fn_index]
+        .iter()
+        .rposition(|l| !l.trim_start().starts_with("#["))
+        .map_or(0, |i| i + 1);
+
+    let table = &lines[table_start..fn_index];
+    if !table.iter().any(|l| l.trim() == "#[rstest]") {
+        return Err(Error::TableNotFound(part));
+    }
+
+    let fn_line = &lines[fn_index];
+    let indent = fn_line[..fn_line.len() - fn_line.trim_start().len()].to_string();
+    let case_line = format!("{indent}{case}");
+
+    let prefix = format!("#[case::{name}(");
+    match table
+        .iter()
+        .position(|l| l.trim_start().starts_with(&prefix))
+    {
+        Some(i) => lines[table_start + i] = case_line,
+        None => lines.insert(fn_index, case_line),
+    }
+
+    let mut result = lines.join("\n");
+    if source.ends_with('\n') {
+        result.push('\n');
+    }
+    Ok(result)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{add_case, case_name, expected_literal, Error};
+
+    const MODULE: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+
+    #[test]
+    fn appends_case_to_table() {
+        let case = "#[case::example_2(read_file_part(\"examples\", DAY, 2), Some(42))]";
+        let result = add_case(MODULE, 2, "example_2", case).unwrap();
+        let expected = [
+            "    #[rstest]",
+            "    #[case::example(advent_of_code::template::read_file(\"examples\", DAY), None)]",
+            "    #[case::example_2(read_file_part(\"examples\", DAY, 2), Some(42))]",
+            "    fn test_part_two(#[case] input: String, #[case] expected: Option<u32>) {",
+        ]
+        .join("\n");
+        assert!(result.contains(&expected));
+        assert_eq!(result.matches("example_2").count(), 1);
+        assert!(result.ends_with('\n'));
+    }
+
+    #[test]
+    fn replaces_case_with_same_name() {
+        let case = "#[case::example(read_file(\"examples\", DAY), Some(42))]";
+        let result = add_case(MODULE, 1, "example", case).unwrap();
+        assert!(result.contains(&format!("    {case}\n    fn test_part_one(")));
+        assert_eq!(result.matches("#[case::example(").count(), 2);
+        assert_eq!(result.lines().count(), MODULE.lines().count());
+    }
+
+    #[test]
+    fn errors_for_plain_tests() {
+        let source = "#[test]\nfn test_part_one() {}\n";
+        assert_eq!(
+            add_case(source, 1, "example", "#[case(1)]"),
+            Err(Error::TableNotFound(1))
+        );
+    }
+
+    #[test]
+    fn errors_for_invalid_part() {
+        assert_eq!(
+            add_case(MODULE, 3, "example", "#[case(1)]"),
+            Err(Error::InvalidPart(3))
+        );
+    }
+
+    #[test]
+    fn formats_expected_values() {
+        assert_eq!(expected_literal("42"), "Some(42)");
+        assert_eq!(expected_literal("-7"), "Some(-7)");
+        assert_eq!(expected_literal("CMZ"), "Some(\"CMZ\".to_string())");
+    }
+
+    #[test]
+    fn names_cases_after_files() {
+        assert_eq!(case_name("data/examples/01.txt"), "example");
+        assert_eq!(case_name("data/examples/01-3.txt"), "example_3");
+    }
+}
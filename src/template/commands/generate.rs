@@ -0,0 +1,23 @@
+use std::process;
+
+use crate::{
+    generators::{self, GENERATED_DAYS},
+    template::Day,
+};
+
+/// Seed used when none is passed, so generated inputs are reproducible by default.
+pub const DEFAULT_SEED: u64 = 2022;
+
+pub fn handle(day: Day, size: usize, seed: Option<u64>) {
+    match generators::generate(day, size, seed.unwrap_or(DEFAULT_SEED)) {
+        Some(input) => println!("{input}"),
+        None => {
+            let days: Vec<_> = GENERATED_DAYS.iter().map(u8::to_string).collect();
+            eprintln!(
+                "No input generator for day {day}. Generators exist for days {}.",
+                days.join(", ")
+            );
+            process::exit(1);
+        }
+    }
+}
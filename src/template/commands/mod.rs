@@ -1,6 +1,11 @@
 pub mod all;
 pub mod download;
+pub mod example;
+pub mod generate;
 pub mod read;
+pub mod readme;
 pub mod scaffold;
 pub mod solve;
 pub mod time;
+#[cfg(feature = "today")]
+pub mod today;
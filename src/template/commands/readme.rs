@@ -0,0 +1,51 @@
+use std::process;
+
+use crate::template::readme_sections::{Error, ManagedReadme};
+use crate::template::timings::Timings;
+use crate::template::{readme_benchmarks, readme_progress};
+
+/// Regenerates the managed sections of the readme from the repository and the stored answers and timings.
+/// With `dry_run`, prints the changes instead of writing them. With `check`, fails if the readme is out of date.
+pub fn handle(dry_run: bool, check: bool) {
+    let readme = match updated_readme() {
+        Ok(readme) => readme,
+        Err(e) => {
+            eprintln!("Failed to update the README: {e}");
+            process::exit(1);
+        }
+    };
+
+    if !readme.is_changed() {
+        println!("README is up to date.");
+        return;
+    }
+
+    if dry_run || check {
+        println!("{}", readme.diff());
+    }
+
+    if check {
+        eprintln!("README is out of date, run `cargo readme` to update it.");
+        process::exit(1);
+    }
+
+    if !dry_run {
+        if let Err(e) = readme.write() {
+            eprintln!("Failed to write the README: {e}");
+            process::exit(1);
+        }
+        println!("Updated README.");
+    }
+}
+
+fn updated_readme() -> Result<ManagedReadme, Error> {
+    let mut readme = ManagedReadme::read()?;
+    readme_progress::update_section(&mut readme)?;
+
+    let timings = Timings::read_from_file();
+    if !timings.data.is_empty() {
+        readme_benchmarks::update_section(&mut readme, &timings)?;
+    }
+
+    Ok(readme)
+}
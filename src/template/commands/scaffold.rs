@@ -4,7 +4,10 @@ use std::{
     process,
 };
 
-use crate::template::Day;
+use crate::template::{
+    manifest::{bin_path, Manifest},
+    Day,
+};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
@@ -24,7 +27,13 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
 pub fn handle(day: Day) {
     let input_path = format!("data/inputs/{day}.txt");
     let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+    let module_path = bin_path(day);
+
+    // read the manifest first, so a broken one aborts before any file is created.
+    let mut manifest = Manifest::read_from_file().unwrap_or_else(|e| {
+        eprintln!("Failed to read the project manifest: {e}");
+        process::exit(1);
+    });
 
     let mut file = match safe_create_file(&module_path) {
         Ok(file) => file,
@@ -68,6 +77,14 @@ pub fn handle(day: Day) {
         }
     }
 
+    manifest.register(day).bin.clone_from(&module_path);
+    manifest.add_example(day, &example_path);
+    if let Err(e) = manifest.store_file() {
+        eprintln!("Failed to update the project manifest: {e}");
+        process::exit(1);
+    }
+    println!("Registered day {day} in \"data/manifest.json\"");
+
     println!("---");
     println!("🎄 Type `cargo solve {day}` to run your solution.");
 }
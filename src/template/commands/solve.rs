@@ -1,8 +1,26 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::{watch::watch, Day};
+
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    count_allocs: bool,
+    submit_part: Option<u8>,
+    is_watch: bool,
+    input: Option<&str>,
+) {
+    if is_watch {
+        watch(day, release);
+        return;
+    }
+
+    if dhat && count_allocs {
+        eprintln!("`--dhat` and `--allocs` both replace the allocator, pass only one of them.");
+        process::exit(1);
+    }
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -12,8 +30,13 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
             "--features".to_string(),
             "dhat-heap".to_string(),
         ]);
-    } else if release {
-        cmd_args.push("--release".to_string());
+    } else {
+        if release {
+            cmd_args.push("--release".to_string());
+        }
+        if count_allocs {
+            cmd_args.extend(["--features".to_string(), "count-allocs".to_string()]);
+        }
     }
 
     cmd_args.push("--".to_string());
@@ -23,6 +46,11 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
+    if let Some(input) = input {
+        cmd_args.push("--input".to_string());
+        cmd_args.push(input.to_string());
+    }
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
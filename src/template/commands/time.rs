@@ -1,40 +1,111 @@
-use std::collections::HashSet;
+use std::process;
 
-use crate::template::run_multi::run_multi;
+use crate::template::benchmark_table::{BenchmarkTable, TableOptions};
+use crate::template::commands::generate::DEFAULT_SEED;
+use crate::template::export;
+use crate::template::manifest::Manifest;
+use crate::template::run_multi::{filter_by_tag, run_multi, RunOptions};
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{readme_benchmarks, readme_progress, scaling, Day, DaySet};
+
+pub use crate::template::{export::Format, scaling::parse_sizes};
+
+pub fn handle(
+    days: Option<DaySet>,
+    tag: Option<&str>,
+    run_all: bool,
+    store: bool,
+    sizes: Option<Vec<usize>>,
+    count_allocs: bool,
+    format: Option<Format>,
+) {
+    let manifest = Manifest::read_from_file().unwrap_or_else(|e| {
+        eprintln!("Failed to read the project manifest: {e}");
+        process::exit(1);
+    });
+
+    if let Some(sizes) = sizes {
+        let Some(day) = days.as_ref().and_then(DaySet::single) else {
+            eprintln!(
+                "`--sizes` needs a single day to time, e.g. `cargo time 16 --sizes 10,20,40`."
+            );
+            process::exit(1);
+        };
+        if manifest.get(day).is_none() {
+            eprintln!("Day {day} has not been scaffolded yet. Run `cargo scaffold {day}` first.");
+            process::exit(1);
+        }
+        return time_sizes(day, &sizes);
+    }
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
     let stored_timings = Timings::read_from_file();
 
-    let days_to_run = day.map_or_else(
-        || {
-            if run_all {
-                all_days().collect()
-            } else {
-                // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
-                    .collect()
-            }
-        },
-        |day| HashSet::from([day]),
-    );
+    let days_to_run = match (days, tag) {
+        (Some(days), _) => days,
+        // a tag selects days explicitly, so fully benched days are timed again.
+        (None, Some(_)) => manifest.days(),
+        (None, None) if run_all => manifest.days(),
+        // when the `--all` flag is not set, filter out days that are fully benched.
+        (None, None) => manifest.days() - stored_timings.complete_days(),
+    };
+
+    let days_to_run = match tag {
+        Some(tag) => filter_by_tag(days_to_run, tag, &manifest).unwrap_or_else(|e| {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        }),
+        None => days_to_run,
+    };
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let options = RunOptions {
+        is_release: true,
+        is_timed: true,
+        count_allocs,
+        // keep stdout free for the formatted timings.
+        progress_to_stderr: format.is_some(),
+    };
+
+    let timings = run_multi(&days_to_run, &manifest, &options).0.unwrap();
+    let merged_timings = stored_timings.merge(&timings);
+    let table_options = TableOptions::from_env();
+
+    if let Some(format) = format {
+        println!(
+            "{}",
+            export::render(format, &timings, &merged_timings, &table_options, &manifest,)
+        );
+    } else if timings.data.len() > 1 {
+        let table = BenchmarkTable::new(&timings, &table_options);
+        println!();
+        println!("{}", table.to_terminal(timings.total_millis()));
+    }
 
     if store {
-        let merged_timings = stored_timings.merge(&timings);
         merged_timings.store_file().unwrap();
 
-        println!();
+        options.print("");
         match readme_benchmarks::update(merged_timings) {
             Ok(()) => {
-                println!("Stored updated benchmarks.");
+                options.print("Stored updated benchmarks.");
             }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
+            Err(e) => {
+                eprintln!("Failed to store updated benchmarks: {e}");
             }
         }
+
+        if let Err(e) = readme_progress::update() {
+            eprintln!("Failed to update progress table: {e}");
+        }
     }
 }
+
+/// Times a day over generated inputs of increasing size and prints how its run time grows.
+fn time_sizes(day: Day, sizes: &[usize]) {
+    let Some(samples) = scaling::run_ladder(day, sizes, DEFAULT_SEED) else {
+        eprintln!("No input generator for day {day}, so it cannot be timed over input sizes.");
+        process::exit(1);
+    };
+
+    println!();
+    println!("{}", scaling::render(&samples));
+}
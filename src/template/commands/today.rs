@@ -0,0 +1,110 @@
+use std::{
+    io::{self, Write},
+    path::Path,
+    process,
+};
+
+use chrono::Datelike;
+
+use crate::template::commands::{download, read, scaffold};
+use crate::template::manifest::{bin_path, Manifest};
+use crate::template::unlock::{self, Clock, SystemClock};
+use crate::template::{aoc_cli, Day, ANSI_BOLD, ANSI_RESET};
+
+/// Download attempts after the unlock. The server tends to be under load in the first seconds,
+/// with the backoff the last attempt happens about half a minute after the unlock.
+const DOWNLOAD_ATTEMPTS: u32 = 6;
+
+pub fn handle(wait: bool) {
+    if wait {
+        wait_and_fetch(&SystemClock);
+        return;
+    }
+
+    match Day::today() {
+        Some(day) => {
+            scaffold::handle(day);
+            download::handle(day);
+            read::handle(day);
+        }
+        None => {
+            eprintln!(
+                "`today` command can only be run between the 1st and \
+                the 25th of december. Please use `scaffold` with a specific day, \
+                or `today --wait` to wait for the next puzzle."
+            );
+            process::exit(1);
+        }
+    }
+}
+
+/// Whether a day has been scaffolded before, i.e. is registered in the manifest or has a module file.
+fn is_scaffolded(day: Day, manifest: &Manifest, file_exists: impl Fn(&str) -> bool) -> bool {
+    manifest.get(day).is_some() || file_exists(&bin_path(day))
+}
+
+/// Scaffolds the next puzzle right away unless it already is, then counts down to its unlock and downloads it as soon as possible.
+fn wait_and_fetch(clock: &impl Clock) {
+    if aoc_cli::check().is_err() {
+        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+        process::exit(1);
+    }
+
+    let (day, unlock_instant) = unlock::next_unlock(clock.now());
+
+    if let Some(year) = aoc_cli::get_year() {
+        if i32::from(year) != unlock_instant.year() {
+            eprintln!(
+                "Warning: AOC_YEAR is set to {year}, so day {day} of {year} will be downloaded instead of {}.",
+                unlock_instant.year()
+            );
+        }
+    }
+
+    let manifest = Manifest::read_from_file().unwrap_or_else(|e| {
+        eprintln!("Failed to read the project manifest: {e}");
+        process::exit(1);
+    });
+    if is_scaffolded(day, &manifest, |path| Path::new(path).exists()) {
+        println!("Day {day} has been scaffolded already.");
+    } else {
+        scaffold::handle(day);
+    }
+    println!();
+
+    unlock::wait_until(clock, unlock_instant, |remaining| {
+        print!(
+            "\r🎄 Day {day} unlocks in {ANSI_BOLD}{}{ANSI_RESET} ",
+            unlock::format_countdown(remaining)
+        );
+        let _ = io::stdout().flush();
+    });
+    println!("\r🎄 Day {day} is unlocked!{:20}", "");
+
+    if let Err(e) = unlock::retry_with_backoff(clock, DOWNLOAD_ATTEMPTS, || aoc_cli::download(day))
+    {
+        eprintln!("failed to call aoc-cli: {e}");
+        process::exit(1);
+    }
+
+    read::handle(day);
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::is_scaffolded;
+    use crate::{day, template::manifest::Manifest};
+
+    #[test]
+    fn skips_scaffolded_days() {
+        let mut manifest = Manifest::default();
+        assert!(!is_scaffolded(day!(5), &manifest, |_| false));
+
+        // a module file without a manifest entry, e.g. from before the manifest existed.
+        assert!(is_scaffolded(day!(5), &manifest, |path| path == "src/bin/05.rs"));
+
+        manifest.register(day!(5));
+        assert!(is_scaffolded(day!(5), &manifest, |_| false));
+        assert!(!is_scaffolded(day!(6), &manifest, |_| false));
+    }
+}
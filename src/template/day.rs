@@ -1,9 +1,10 @@
 use std::error::Error;
 use std::fmt::Display;
+use std::ops::{BitAnd, BitOr, RangeInclusive, Sub};
 use std::str::FromStr;
 
 #[cfg(feature = "today")]
-use chrono::{Datelike, FixedOffset, Utc};
+use chrono::{DateTime, Datelike, FixedOffset, TimeZone, Utc};
 
 #[cfg(feature = "today")]
 const SERVER_UTC_OFFSET: i32 = -5;
@@ -47,14 +48,32 @@ impl Day {
 impl Day {
     /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
     pub fn today() -> Option<Self> {
-        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
-        let today = Utc::now().with_timezone(&offset);
+        Self::today_at(Utc::now())
+    }
+
+    /// Returns the day of advent at the given instant, in the timezone of the Advent of Code server.
+    pub fn today_at(now: DateTime<Utc>) -> Option<Self> {
+        let today = now.with_timezone(&server_offset());
         if today.month() == 12 && today.day() <= 25 {
             Self::new(u8::try_from(today.day()).ok()?)
         } else {
             None
         }
     }
+
+    /// Returns the instant the puzzle of this day unlocks in the given year, i.e. midnight at the server.
+    pub fn unlock_instant(self, year: i32) -> DateTime<Utc> {
+        server_offset()
+            .with_ymd_and_hms(year, 12, self.0.into(), 0, 0, 0)
+            .single()
+            .expect("a fixed offset has no ambiguous local times")
+            .with_timezone(&Utc)
+    }
+}
+
+#[cfg(feature = "today")]
+pub(crate) fn server_offset() -> FixedOffset {
+    FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600).expect("server offset to be in range")
 }
 
 impl Display for Day {
@@ -134,6 +153,203 @@ impl Iterator for AllDays {
 
 /* -------------------------------------------------------------------------- */
 
+/// A set of days of advent, e.g. parsed from a day list such as `1-5,12,20-`.
+///
+/// Iterates in ascending order and displays in the same compact syntax it parses.
+///
+/// ```
+/// # use advent_of_code::template::DaySet;
+/// let days: DaySet = "1-3,12,20-".parse().unwrap();
+/// assert_eq!(days.len(), 10);
+/// assert_eq!(days.to_string(), "1-3,12,20-25");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct DaySet(u32);
+
+impl DaySet {
+    /// Creates a set without any days.
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Creates a set with every day from the 1st to the 25th.
+    pub fn all() -> Self {
+        all_days().collect()
+    }
+
+    pub fn insert(&mut self, day: Day) {
+        self.0 |= 1 << day.0;
+    }
+
+    pub fn remove(&mut self, day: Day) {
+        self.0 &= !(1 << day.0);
+    }
+
+    pub fn contains(&self, day: Day) -> bool {
+        self.0 & (1 << day.0) != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns the day if the set contains exactly one.
+    pub fn single(&self) -> Option<Day> {
+        match self.len() {
+            1 => self.iter().next(),
+            _ => None,
+        }
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    /// Days in `self` that are not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+
+    /// Iterates over the days in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = Day> + '_ {
+        all_days().filter(|day| self.contains(*day))
+    }
+}
+
+impl From<Day> for DaySet {
+    fn from(day: Day) -> Self {
+        Self(1 << day.0)
+    }
+}
+
+impl From<RangeInclusive<Day>> for DaySet {
+    fn from(range: RangeInclusive<Day>) -> Self {
+        all_days().filter(|day| range.contains(day)).collect()
+    }
+}
+
+impl FromIterator<Day> for DaySet {
+    fn from_iter<T: IntoIterator<Item = Day>>(iter: T) -> Self {
+        let mut set = Self::empty();
+        for day in iter {
+            set.insert(day);
+        }
+        set
+    }
+}
+
+impl IntoIterator for DaySet {
+    type Item = Day;
+    type IntoIter = std::vec::IntoIter<Day>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter().collect::<Vec<_>>().into_iter()
+    }
+}
+
+impl BitOr for DaySet {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.union(&rhs)
+    }
+}
+
+impl BitAnd for DaySet {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.intersection(&rhs)
+    }
+}
+
+impl Sub for DaySet {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.difference(&rhs)
+    }
+}
+
+impl Display for DaySet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let days: Vec<u8> = self.iter().map(Day::into_inner).collect();
+        let mut ranges: Vec<String> = Vec::new();
+
+        let mut i = 0;
+        while i < days.len() {
+            let start = days[i];
+            while i + 1 < days.len() && days[i + 1] == days[i] + 1 {
+                i += 1;
+            }
+            match days[i] {
+                end if end == start => ranges.push(start.to_string()),
+                end => ranges.push(format!("{start}-{end}")),
+            }
+            i += 1;
+        }
+
+        f.write_str(&ranges.join(","))
+    }
+}
+
+impl FromStr for DaySet {
+    type Err = DaySetFromStrError;
+
+    /// Parses a comma separated list of days and ranges. Open ranges such as `20-` or `-5` extend to the
+    /// last or first day of advent.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || DaySetFromStrError(s.to_string());
+        let parse_day = |day: &str, default: Day| match day.trim() {
+            "" => Ok(default),
+            day => day.parse::<Day>().map_err(|_| err()),
+        };
+
+        let mut set = Self::empty();
+        for part in s.split(',') {
+            match part.split_once('-') {
+                Some((start, end)) => {
+                    let start = parse_day(start, Day(1))?;
+                    let end = parse_day(end, Day(25))?;
+                    if start > end {
+                        return Err(err());
+                    }
+                    set = set | DaySet::from(start..=end);
+                }
+                None => set.insert(part.trim().parse().map_err(|_| err())?),
+            }
+        }
+
+        Ok(set)
+    }
+}
+
+/// An error which can be returned when parsing a [`DaySet`].
+#[derive(Debug)]
+pub struct DaySetFromStrError(String);
+
+impl Error for DaySetFromStrError {}
+
+impl Display for DaySetFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid day list \"{}\", expecting days between 1 and 25 such as \"1-5,12,20-\"",
+            self.0
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 /// Creates a [`Day`] value in a const context.
 #[macro_export]
 macro_rules! day {
@@ -154,7 +370,7 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{all_days, Day, DaySet};
 
     #[test]
     fn all_days_iterator() {
@@ -187,6 +403,53 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn parses_day_lists() {
+        let days: DaySet = "1-5,12,20-".parse().unwrap();
+        let expected: Vec<u8> = vec![1, 2, 3, 4, 5, 12, 20, 21, 22, 23, 24, 25];
+        assert_eq!(
+            days.iter().map(Day::into_inner).collect::<Vec<_>>(),
+            expected
+        );
+
+        assert_eq!("-3".parse::<DaySet>().unwrap().to_string(), "1-3");
+        assert_eq!(" 7 , 3 ".parse::<DaySet>().unwrap().to_string(), "3,7");
+        assert_eq!("8".parse::<DaySet>().unwrap().single(), Some(Day(8)));
+        assert_eq!("-".parse::<DaySet>().unwrap(), DaySet::all());
+
+        for invalid in ["", "0", "26", "5-3", "1,,2", "a-b", "1-30"] {
+            assert!(invalid.parse::<DaySet>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn displays_day_lists() {
+        let days: DaySet = [Day(1), Day(2), Day(3), Day(5), Day(7), Day(8)]
+            .into_iter()
+            .collect();
+        assert_eq!(days.to_string(), "1-3,5,7-8");
+        assert_eq!(days.to_string().parse::<DaySet>().unwrap(), days);
+        assert_eq!(DaySet::empty().to_string(), "");
+        assert_eq!(DaySet::all().to_string(), "1-25");
+    }
+
+    #[test]
+    fn combines_day_sets() {
+        let a = DaySet::from(Day(1)..=Day(5));
+        let b: DaySet = "4-8".parse().unwrap();
+
+        assert_eq!((a | b).to_string(), "1-8");
+        assert_eq!((a & b).to_string(), "4-5");
+        assert_eq!((a - b).to_string(), "1-3");
+        assert_eq!(DaySet::all().difference(&a).len(), 20);
+
+        let mut set = DaySet::from(Day(25));
+        assert!(set.contains(Day(25)));
+        set.remove(Day(25));
+        assert!(set.is_empty());
+        assert_eq!(set.single(), None);
+    }
 }
 
 /* -------------------------------------------------------------------------- */
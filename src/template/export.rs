@@ -0,0 +1,241 @@
+/// Module that prints timings in a machine- or human-readable format, for `cargo time --format`.
+use std::{collections::HashMap, str::FromStr};
+
+use tinyjson::JsonValue;
+
+use crate::template::alloc_counter::AllocStats;
+use crate::template::benchmark_table::{BenchmarkTable, TableOptions};
+use crate::template::manifest::Manifest;
+use crate::template::run_multi::child_commands::parse_duration;
+use crate::template::timings::{Timing, Timings};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// The aligned table that `cargo time` prints to the terminal.
+    Table,
+    Csv,
+    Json,
+    /// The table as it appears in the readme.
+    Markdown,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(Format::Table),
+            "csv" => Ok(Format::Csv),
+            "json" => Ok(Format::Json),
+            "markdown" => Ok(Format::Markdown),
+            _ => Err(format!(
+                "unknown format \"{s}\", expected one of table, csv, json or markdown."
+            )),
+        }
+    }
+}
+
+/// Renders the timings of the current run, followed by the stored timings merged with the run.
+pub fn render(
+    format: Format,
+    run: &Timings,
+    merged: &Timings,
+    options: &TableOptions,
+    manifest: &Manifest,
+) -> String {
+    match format {
+        Format::Table => [
+            "Current run".to_string(),
+            BenchmarkTable::new(run, options).to_terminal(run.total_millis()),
+            String::new(),
+            "All timings".to_string(),
+            BenchmarkTable::new(merged, options).to_terminal(merged.total_millis()),
+        ]
+        .join("\n"),
+        Format::Markdown => {
+            let mut lines = vec!["### Current run".to_string(), String::new()];
+            lines.extend(
+                BenchmarkTable::new(run, options).to_markdown(run.total_millis(), manifest),
+            );
+            lines.extend([String::new(), "### All timings".to_string(), String::new()]);
+            lines.extend(
+                BenchmarkTable::new(merged, options).to_markdown(merged.total_millis(), manifest),
+            );
+            lines.join("\n")
+        }
+        Format::Csv => to_csv(run, merged),
+        Format::Json => {
+            let mut map: HashMap<String, JsonValue> = HashMap::new();
+            map.insert("run".into(), JsonValue::from(run.clone()));
+            map.insert("merged".into(), JsonValue::from(merged.clone()));
+            JsonValue::Object(map)
+                .format()
+                .expect("timings to serialize to JSON")
+        }
+    }
+}
+
+/// One row per day and set (`run` or `merged`). Durations are plain nanoseconds so spreadsheets can sum them.
+fn to_csv(run: &Timings, merged: &Timings) -> String {
+    let mut lines = vec![[
+        "set",
+        "day",
+        "parse_nanos",
+        "part_1_nanos",
+        "part_2_nanos",
+        "total_nanos",
+        "parse_allocs",
+        "parse_bytes",
+        "parse_peak",
+        "part_1_allocs",
+        "part_1_bytes",
+        "part_1_peak",
+        "part_2_allocs",
+        "part_2_bytes",
+        "part_2_peak",
+    ]
+    .join(",")];
+
+    for (set, timings) in [("run", run), ("merged", merged)] {
+        lines.extend(timings.data.iter().map(|timing| csv_row(set, timing)));
+    }
+
+    lines.join("\n")
+}
+
+fn csv_row(set: &str, timing: &Timing) -> String {
+    let nanos = |time: &Option<String>| {
+        time.as_deref()
+            .and_then(parse_duration)
+            .map_or(String::new(), |n| format!("{n:.0}"))
+    };
+
+    let allocs = |allocs: Option<AllocStats>| match allocs {
+        Some(a) => format!("{},{},{}", a.allocations, a.bytes, a.peak),
+        None => ",,".into(),
+    };
+
+    [
+        set.to_string(),
+        timing.day.to_string(),
+        nanos(&timing.parse),
+        nanos(&timing.part_1),
+        nanos(&timing.part_2),
+        format!("{:.0}", timing.total_nanos),
+        allocs(timing.parse_allocs),
+        allocs(timing.part_1_allocs),
+        allocs(timing.part_2_allocs),
+    ]
+    .join(",")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{collections::HashMap, str::FromStr};
+
+    use tinyjson::JsonValue;
+
+    use super::{render, Format};
+    use crate::template::{
+        alloc_counter::AllocStats,
+        benchmark_table::TableOptions,
+        manifest::Manifest,
+        timings::{Timing, Timings},
+        Day,
+    };
+
+    fn manifest() -> Manifest {
+        let mut manifest = Manifest::default();
+        manifest.register(Day::new(2).unwrap());
+        manifest
+    }
+
+    fn timings(days: &[u8]) -> Timings {
+        Timings {
+            data: days
+                .iter()
+                .map(|&day| Timing {
+                    day: Day::new(day).unwrap(),
+                    parse: None,
+                    part_1: Some("1.5µs".into()),
+                    part_2: None,
+                    total_nanos: 1500.0,
+                    parse_allocs: None,
+                    part_1_allocs: Some(AllocStats {
+                        allocations: 2,
+                        bytes: 48,
+                        peak: 32,
+                    }),
+                    part_2_allocs: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!(Format::from_str("csv"), Ok(Format::Csv));
+        assert_eq!(Format::from_str("markdown"), Ok(Format::Markdown));
+        assert!(Format::from_str("yaml").is_err());
+    }
+
+    #[test]
+    fn renders_csv() {
+        let csv = render(
+            Format::Csv,
+            &timings(&[2]),
+            &timings(&[1, 2]),
+            &TableOptions::default(),
+            &manifest(),
+        );
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("set,day,parse_nanos,part_1_nanos"));
+        assert_eq!(lines[1], "run,02,,1500,,1500,,,,2,48,32,,,");
+        assert_eq!(lines[2], "merged,01,,1500,,1500,,,,2,48,32,,,");
+        assert!(lines
+            .iter()
+            .all(|l| l.matches(',').count() == lines[0].matches(',').count()));
+    }
+
+    #[test]
+    fn renders_json() {
+        let json = render(
+            Format::Json,
+            &timings(&[2]),
+            &timings(&[1, 2]),
+            &TableOptions::default(),
+            &manifest(),
+        );
+        let value: JsonValue = json.parse().unwrap();
+        let map = value.get::<HashMap<String, JsonValue>>().unwrap();
+        let merged = Timings::try_from(map["merged"].stringify().unwrap()).unwrap();
+        assert_eq!(merged.data.len(), 2);
+        let run = Timings::try_from(map["run"].stringify().unwrap()).unwrap();
+        assert_eq!(run.data[0].part_1_allocs.unwrap().bytes, 48);
+    }
+
+    #[test]
+    fn renders_both_tables() {
+        let markdown = render(
+            Format::Markdown,
+            &timings(&[2]),
+            &timings(&[1, 2]),
+            &TableOptions::default(),
+            &manifest(),
+        );
+        assert!(markdown.starts_with("### Current run\n\n| Day |"));
+        assert!(markdown.contains("### All timings"));
+        assert_eq!(markdown.matches("| [Day 2](./src/bin/02.rs)").count(), 2);
+
+        let table = render(
+            Format::Table,
+            &timings(&[2]),
+            &timings(&[1, 2]),
+            &TableOptions::default(),
+            &manifest(),
+        );
+        assert!(table.starts_with("Current run\n"));
+        assert_eq!(table.matches("Day 01").count(), 1);
+    }
+}
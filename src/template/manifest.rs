@@ -0,0 +1,438 @@
+/// Module that keeps track of the days of this project: their solution binary, example files,
+/// recorded answers and tags. It is stored in `data/manifest.json` and kept up to date by `cargo scaffold`,
+/// `cargo example add` and `cargo all --record`. If the file does not exist, it is generated from the project files,
+/// after that it is the only place the template looks up days.
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    path::Path,
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    all_days,
+    answers::{Answer, Answers},
+    Day, DaySet,
+};
+
+static MANIFEST_FILE_PATH: &str = "./data/manifest.json";
+
+/// Answers were recorded in a separate file before the manifest existed.
+/// They are imported when the manifest is generated.
+static LEGACY_ANSWERS_FILE_PATH: &str = "./data/answers.json";
+
+/// Path of the solution binary that `cargo scaffold` creates for a day.
+#[must_use]
+pub fn bin_path(day: Day) -> String {
+    format!("src/bin/{day}.rs")
+}
+
+/// Everything the template knows about a single day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DayEntry {
+    pub day: Day,
+    /// Path of the solution binary, relative to the project root.
+    pub bin: String,
+    /// Paths of the example inputs, relative to the project root.
+    pub examples: Vec<String>,
+    /// Answers for the real input, recorded with `cargo all --record`.
+    pub answer: Answer,
+    pub tags: Vec<String>,
+}
+
+impl DayEntry {
+    #[must_use]
+    pub fn new(day: Day) -> Self {
+        DayEntry {
+            day,
+            bin: bin_path(day),
+            examples: vec![],
+            answer: Answer {
+                day,
+                part_1: None,
+                part_2: None,
+            },
+            tags: vec![],
+        }
+    }
+
+    /// Link to the solution binary, relative to the readme.
+    #[must_use]
+    pub fn link(&self) -> String {
+        format!("./{}", self.bin)
+    }
+}
+
+/// Represents all days that have been scaffolded, ordered by day.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Manifest {
+    pub days: Vec<DayEntry>,
+}
+
+impl Manifest {
+    /// Dehydrate the manifest to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        fs::write(MANIFEST_FILE_PATH, self.to_json())
+    }
+
+    /// Formats the manifest as JSON with a stable key order, so it diffs well under version control.
+    pub fn to_json(&self) -> String {
+        let days: Vec<String> = self.days.iter().map(DayEntry::to_json).collect();
+        format!("{{\n  \"days\": [\n{}\n  ]\n}}\n", days.join(",\n"))
+    }
+
+    /// Rehydrate the manifest from a JSON file. If not present, generates it from the project files and stores it.
+    /// Fails if the file exists but cannot be read or parsed, so a broken manifest is never replaced by an empty one.
+    pub fn read_from_file() -> Result<Self, String> {
+        let s = match fs::read_to_string(MANIFEST_FILE_PATH) {
+            Ok(s) => s,
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                let manifest = Manifest::discover();
+                match manifest.store_file() {
+                    Ok(()) => {
+                        eprintln!("Generated \"{MANIFEST_FILE_PATH}\" from the project files.")
+                    }
+                    Err(e) => eprintln!("Failed to store generated manifest: {e}"),
+                }
+                return Ok(manifest);
+            }
+            Err(e) => return Err(format!("{MANIFEST_FILE_PATH}: {e}")),
+        };
+
+        Manifest::try_from(s).map_err(|e| format!("{MANIFEST_FILE_PATH}: {e}"))
+    }
+
+    /// Generates a manifest from the solution binaries and example files that exist, and the answers
+    /// recorded before the manifest existed.
+    pub fn discover() -> Self {
+        let mut manifest = Manifest::default();
+
+        for day in all_days().filter(|day| Path::new(&bin_path(*day)).exists()) {
+            manifest.register(day).examples = discover_examples(day);
+        }
+
+        if let Some(answers) = fs::read_to_string(LEGACY_ANSWERS_FILE_PATH)
+            .ok()
+            .and_then(|s| Answers::try_from(s).ok())
+        {
+            manifest.set_answers(&answers);
+        }
+
+        manifest
+    }
+
+    /// Returns the entry of a day, if it has been scaffolded.
+    pub fn get(&self, day: Day) -> Option<&DayEntry> {
+        self.days.iter().find(|entry| entry.day == day)
+    }
+
+    /// Returns the days that have been scaffolded.
+    pub fn days(&self) -> DaySet {
+        self.days.iter().map(|entry| entry.day).collect()
+    }
+
+    /// Returns the days that carry a tag.
+    pub fn tagged(&self, tag: &str) -> DaySet {
+        self.days
+            .iter()
+            .filter(|entry| entry.tags.iter().any(|t| t == tag))
+            .map(|entry| entry.day)
+            .collect()
+    }
+
+    /// Returns all tags in use, sorted and without duplicates.
+    pub fn tags(&self) -> Vec<&str> {
+        let mut tags: Vec<&str> = self
+            .days
+            .iter()
+            .flat_map(|entry| entry.tags.iter().map(String::as_str))
+            .collect();
+        tags.sort_unstable();
+        tags.dedup();
+        tags
+    }
+
+    /// Returns the entry of a day, adding it if the day has not been registered yet.
+    pub fn register(&mut self, day: Day) -> &mut DayEntry {
+        let index = match self.days.binary_search_by_key(&day, |entry| entry.day) {
+            Ok(index) => index,
+            Err(index) => {
+                self.days.insert(index, DayEntry::new(day));
+                index
+            }
+        };
+        &mut self.days[index]
+    }
+
+    pub fn add_example(&mut self, day: Day, path: &str) {
+        let examples = &mut self.register(day).examples;
+        if !examples.iter().any(|example| example == path) {
+            examples.push(path.into());
+        }
+    }
+
+    /// Returns the recorded answers of all days that have at least one.
+    pub fn answers(&self) -> Answers {
+        Answers {
+            data: self
+                .days
+                .iter()
+                .filter(|entry| entry.answer.part_1.is_some() || entry.answer.part_2.is_some())
+                .map(|entry| entry.answer.clone())
+                .collect(),
+        }
+    }
+
+    /// Records answers, overwriting the previous answers of the same days.
+    pub fn set_answers(&mut self, answers: &Answers) {
+        for answer in &answers.data {
+            self.register(answer.day).answer = answer.clone();
+        }
+    }
+}
+
+/// Finds the example files of a day: the default `{day}.txt` first, then `{day}-{n}.txt` in order.
+fn discover_examples(day: Day) -> Vec<String> {
+    let default_name = format!("{day}.txt");
+    let prefix = format!("{day}-");
+
+    let mut names: Vec<String> = fs::read_dir("data/examples")
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| {
+            *name == default_name || (name.starts_with(&prefix) && name.ends_with(".txt"))
+        })
+        .collect();
+
+    names.sort_by_key(|name| (name.len(), name.clone()));
+    names
+        .into_iter()
+        .map(|name| format!("data/examples/{name}"))
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl TryFrom<String> for Manifest {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_days = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("days")
+            .ok_or("expected JSON document to have key `days`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.days` to be an array.")?;
+
+        let mut days: Vec<DayEntry> = json_days
+            .iter()
+            .map(DayEntry::try_from)
+            .collect::<Result<_, _>>()?;
+
+        days.sort_unstable_by_key(|entry| entry.day);
+        Ok(Manifest { days })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn string_to_json(s: &str) -> String {
+    JsonValue::String(s.into())
+        .stringify()
+        .expect("strings to serialize to JSON")
+}
+
+fn strings_to_json(strings: &[String]) -> String {
+    let strings: Vec<String> = strings.iter().map(|s| string_to_json(s)).collect();
+    format!("[{}]", strings.join(", "))
+}
+
+fn optional_string_to_json(value: Option<&String>) -> String {
+    value.map_or("null".into(), |s| string_to_json(s))
+}
+
+impl DayEntry {
+    fn to_json(&self) -> String {
+        [
+            "    {".to_string(),
+            format!("      \"day\": {},", string_to_json(&self.day.to_string())),
+            format!("      \"bin\": {},", string_to_json(&self.bin)),
+            format!("      \"examples\": {},", strings_to_json(&self.examples)),
+            format!("      \"tags\": {},", strings_to_json(&self.tags)),
+            format!(
+                "      \"answers\": {{ \"part_1\": {}, \"part_2\": {} }}",
+                optional_string_to_json(self.answer.part_1.as_ref()),
+                optional_string_to_json(self.answer.part_2.as_ref())
+            ),
+            "    }".to_string(),
+        ]
+        .join("\n")
+    }
+}
+
+impl TryFrom<&JsonValue> for DayEntry {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected day to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected day.day to be a Day struct.")?;
+
+        let bin = json
+            .get("bin")
+            .and_then(|v| v.get::<String>())
+            .ok_or(format!("Expected day {day}: bin to be a string."))?;
+
+        let strings = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<Vec<JsonValue>>())
+                .and_then(|values| {
+                    values
+                        .iter()
+                        .map(|v| v.get::<String>().cloned())
+                        .collect::<Option<Vec<_>>>()
+                })
+                .ok_or(format!(
+                    "Expected day {day}: {key} to be an array of strings."
+                ))
+        };
+
+        let answers = json
+            .get("answers")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .ok_or(format!("Expected day {day}: answers to be a JSON object."))?;
+
+        let part = |key: &str| {
+            answers
+                .get(key)
+                .map(|v| if v.is_null() { None } else { v.get::<String>() })
+                .ok_or(format!(
+                    "Expected day {day}: answers.{key} to be null or string."
+                ))
+        };
+
+        Ok(DayEntry {
+            day,
+            bin: bin.clone(),
+            examples: strings("examples")?,
+            answer: Answer {
+                day,
+                part_1: part("part_1")?.cloned(),
+                part_2: part("part_2")?.cloned(),
+            },
+            tags: strings("tags")?,
+        })
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{DayEntry, Manifest};
+    use crate::{
+        day,
+        template::answers::{Answer, Answers},
+    };
+
+    fn get_mock_manifest() -> Manifest {
+        let mut manifest = Manifest::default();
+        manifest.add_example(day!(9), "data/examples/09.txt");
+        manifest.add_example(day!(9), "data/examples/09-2.txt");
+        manifest.register(day!(1)).tags = vec!["parsing".into()];
+        manifest.set_answers(&Answers {
+            data: vec![Answer {
+                day: day!(1),
+                part_1: Some("24000".into()),
+                part_2: None,
+            }],
+        });
+        manifest
+    }
+
+    #[test]
+    fn registers_days_in_order() {
+        let manifest = get_mock_manifest();
+        assert_eq!(manifest.days().to_string(), "1,9");
+        assert_eq!(manifest.days[0].day, day!(1));
+        assert_eq!(manifest.get(day!(9)).unwrap().bin, "src/bin/09.rs");
+        assert_eq!(manifest.get(day!(9)).unwrap().link(), "./src/bin/09.rs");
+        assert_eq!(manifest.get(day!(2)), None);
+    }
+
+    #[test]
+    fn filters_by_tag() {
+        let mut manifest = get_mock_manifest();
+        manifest.register(day!(12)).tags = vec!["grid".into(), "search".into()];
+        manifest.register(day!(16)).tags = vec!["search".into()];
+
+        assert_eq!(manifest.tagged("search").to_string(), "12,16");
+        assert_eq!(manifest.tagged("grid").to_string(), "12");
+        assert!(manifest.tagged("winnow").is_empty());
+        assert_eq!(manifest.tags(), ["grid", "parsing", "search"]);
+    }
+
+    #[test]
+    fn adds_examples_once() {
+        let mut manifest = get_mock_manifest();
+        manifest.add_example(day!(9), "data/examples/09-2.txt");
+        assert_eq!(
+            manifest.get(day!(9)).unwrap().examples,
+            ["data/examples/09.txt", "data/examples/09-2.txt"]
+        );
+    }
+
+    #[test]
+    fn records_answers() {
+        let mut manifest = get_mock_manifest();
+        assert_eq!(manifest.answers().data.len(), 1);
+        assert_eq!(
+            manifest.answers().get(day!(1)).unwrap().part_1.as_deref(),
+            Some("24000")
+        );
+
+        manifest.set_answers(&Answers {
+            data: vec![Answer {
+                day: day!(1),
+                part_1: Some("24000".into()),
+                part_2: Some("45000".into()),
+            }],
+        });
+        assert_eq!(
+            manifest.answers().get(day!(1)).unwrap().part(2),
+            Some(&"45000".to_string())
+        );
+        assert_eq!(manifest.days().len(), 2);
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let manifest = get_mock_manifest();
+        let json = manifest.to_json();
+        assert!(json.contains(
+            "      \"day\": \"01\",\n      \"bin\": \"src/bin/01.rs\",\n      \"examples\": [],\n      \"tags\": [\"parsing\"],\n      \"answers\": { \"part_1\": \"24000\", \"part_2\": null }"
+        ));
+        assert_eq!(Manifest::try_from(json), Ok(manifest));
+    }
+
+    #[test]
+    fn rejects_invalid_entries() {
+        let entry = DayEntry::new(day!(3));
+        let json = entry.to_json().replace("\"tags\": []", "\"tags\": [1]");
+        let error = Manifest::try_from(format!("{{\"days\":[{json}]}}")).unwrap_err();
+        assert_eq!(error, "Expected day 03: tags to be an array of strings.");
+        assert!(Manifest::try_from("{}".to_string()).is_err());
+    }
+}
@@ -1,15 +1,26 @@
 use std::{env, fs};
 
+pub mod alloc_counter;
+pub mod answers;
 pub mod aoc_cli;
 pub mod commands;
+pub mod manifest;
 pub mod runner;
 
 pub use day::*;
 
+mod benchmark_table;
 mod day;
+mod export;
 mod readme_benchmarks;
+mod readme_progress;
+mod readme_sections;
 mod run_multi;
+mod scaling;
 mod timings;
+#[cfg(feature = "today")]
+mod unlock;
+mod watch;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
@@ -24,6 +35,22 @@ pub fn read_file(folder: &str, day: Day) -> String {
     f.expect("could not open input file")
 }
 
+/// Reads the puzzle input for a day. If the solution was invoked with `--input <path>`, that file is read instead.
+#[must_use]
+pub fn read_input(day: Day) -> String {
+    let args: Vec<String> = env::args().collect();
+
+    match args.iter().position(|x| x == "--input") {
+        Some(index) => {
+            let path = args
+                .get(index + 1)
+                .expect("`--input` to be followed by a path");
+            fs::read_to_string(path).expect("could not open input file")
+        }
+        None => read_file("inputs", day),
+    }
+}
+
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
@@ -39,6 +66,9 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// With `parse = <function>`, the input is parsed once by the given function and both parts receive
+/// a reference to its output instead of the raw input. Parsing is then timed separately from the parts.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
@@ -50,8 +80,38 @@ macro_rules! solution {
     ($day:expr, 2) => {
         $crate::solution!(@impl $day, [part_two, 2]);
     };
+    ($day:expr, parse = $parse:expr) => {
+        $crate::solution!(@impl_parsed $day, $parse, [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, parse = $parse:expr, 1) => {
+        $crate::solution!(@impl_parsed $day, $parse, [part_one, 1]);
+    };
+    ($day:expr, parse = $parse:expr, 2) => {
+        $crate::solution!(@impl_parsed $day, $parse, [part_two, 2]);
+    };
 
     (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@setup $day);
+
+        fn main() {
+            use $crate::template::runner::*;
+            let input = $crate::template::read_input(DAY);
+            $( run_part($func, &input, DAY, $part); )*
+        }
+    };
+
+    (@impl_parsed $day:expr, $parse:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@setup $day);
+
+        fn main() {
+            use $crate::template::runner::*;
+            let input = $crate::template::read_input(DAY);
+            let parsed = run_parse($parse, input.as_str());
+            $( run_part($func, &parsed, DAY, $part); )*
+        }
+    };
+
+    (@setup $day:expr) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
@@ -59,10 +119,9 @@ macro_rules! solution {
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
-        fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
-        }
+        #[cfg(all(feature = "count-allocs", not(feature = "dhat-heap")))]
+        #[global_allocator]
+        static ALLOC: $crate::template::alloc_counter::CountingAlloc =
+            $crate::template::alloc_counter::CountingAlloc;
     };
 }
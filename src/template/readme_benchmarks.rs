@@ -1,126 +1,110 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
-
+use crate::template::benchmark_table::{BenchmarkTable, TableOptions};
+use crate::template::manifest::Manifest;
+use crate::template::readme_sections::{replace_section, Error, ManagedReadme, Section};
 use crate::template::timings::Timings;
-use crate::template::Day;
-
-static MARKER: &str = "<!--- benchmarking table --->";
-
-#[derive(Debug)]
-pub enum Error {
-    Parser(String),
-    IO(io::Error),
-}
-
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
-    }
-}
 
-pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
-}
-
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
+pub const SECTION: Section = Section {
+    name: "benchmarks",
+    legacy_marker: Some("<!--- benchmarking table --->"),
+};
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
-
-    if matches.len() > 2 {
-        return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
-        ));
-    }
-
-    let pos_start = matches
-        .first()
-        .map(|m| m.0)
-        .ok_or_else(|| Error::Parser("Could not find table start position.".into()))?;
-
-    let pos_end = matches
-        .last()
-        .map(|m| m.0 + m.1.len())
-        .ok_or_else(|| Error::Parser("Could not find table end position.".into()))?;
-
-    Ok(TablePosition { pos_start, pos_end })
-}
-
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
+fn construct_table(
+    prefix: &str,
+    timings: &Timings,
+    total_millis: f64,
+    options: &TableOptions,
+    manifest: &Manifest,
+) -> String {
     let header = format!("{prefix} Benchmarks");
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
-
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
-    }
-
-    lines.push(String::new());
-    lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    let mut lines: Vec<String> = vec![header, String::new()];
+    lines.extend(BenchmarkTable::new(timings, options).to_markdown(total_millis, manifest));
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
-    s.replace_range(positions.pos_start..positions.pos_end, &table);
-    Ok(())
+fn update_content(
+    s: &mut String,
+    timings: &Timings,
+    total_millis: f64,
+    options: &TableOptions,
+    manifest: &Manifest,
+) -> Result<(), Error> {
+    let table = construct_table("##", timings, total_millis, options, manifest);
+    replace_section(s, &SECTION, &table)
+}
+
+/// Puts the benchmark table into the readme, without writing it.
+pub fn update_section(readme: &mut ManagedReadme, timings: &Timings) -> Result<(), Error> {
+    let manifest = Manifest::read_from_file().map_err(Error::Parser)?;
+    update_content(
+        readme.content_mut(),
+        timings,
+        timings.total_millis(),
+        &TableOptions::from_env(),
+        &manifest,
+    )
 }
 
 pub fn update(timings: Timings) -> Result<(), Error> {
-    let path = "README.md";
-    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
-    fs::write(path, &readme)?;
-    Ok(())
+    let mut readme = ManagedReadme::read()?;
+    update_section(&mut readme, &timings)?;
+    readme.write()
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{update_content, TableOptions};
+    use crate::{
+        day, template::alloc_counter::AllocStats, template::manifest::Manifest,
+        template::timings::Timing, template::timings::Timings,
+    };
+
+    const START: &str = "<!-- aoc:benchmarks:start -->";
+    const END: &str = "<!-- aoc:benchmarks:end -->";
+
+    fn manifest() -> Manifest {
+        let mut manifest = Manifest::default();
+        for day in [day!(1), day!(2), day!(4)] {
+            manifest.register(day);
+        }
+        manifest
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    parse_allocs: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
                 },
                 Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    parse_allocs: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
                 },
                 Timing {
                     day: day!(4),
+                    parse: None,
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
                     total_nanos: 9e+10,
+                    parse_allocs: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
                 },
             ],
         }
@@ -130,53 +114,179 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            &get_mock_timings(),
+            190.0,
+            &TableOptions::default(),
+            &manifest(),
+        )
+        .unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
-        let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        let mut s = format!("{}\n{}\n{}", START, START, END);
+        update_content(
+            &mut s,
+            &get_mock_timings(),
+            190.0,
+            &TableOptions::default(),
+            &manifest(),
+        )
+        .unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
-        let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", START, END);
+        update_content(
+            &mut s,
+            &get_mock_timings(),
+            190.0,
+            &TableOptions::default(),
+            &manifest(),
+        )
+        .unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
-        let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", START, END);
+        update_content(
+            &mut s,
+            &get_mock_timings(),
+            190.0,
+            &TableOptions::default(),
+            &manifest(),
+        )
+        .unwrap();
+        update_content(
+            &mut s,
+            &get_mock_timings(),
+            190.0,
+            &TableOptions::default(),
+            &manifest(),
+        )
+        .unwrap();
+        assert_eq!(s.matches(START).collect::<Vec<&str>>().len(), 1);
+        assert_eq!(s.matches(END).collect::<Vec<&str>>().len(), 1);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
 
     #[test]
     fn format_benchmarks() {
-        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", START, END);
+        update_content(
+            &mut s,
+            &get_mock_timings(),
+            190.0,
+            &TableOptions::default(),
+            &manifest(),
+        )
+        .unwrap();
         let expected = [
             "foo",
             "bar",
-            "<!--- benchmarking table --->",
+            "<!-- aoc:benchmarks:start -->",
             "## Benchmarks",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| Day | Part 1 | Part 2 | Share | Cost |",
+            "| :---: | :---: | :---: | :---: | :---: |",
+            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` | 15.8% | `██████▋` |",
+            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` | 36.8% | `███████████████▌` |",
+            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` | 47.4% | `████████████████████` |",
             "",
             "**Total: 190.00ms**",
-            "<!--- benchmarking table --->",
+            "<!-- aoc:benchmarks:end -->",
             "baz",
         ]
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn migrates_legacy_marker() {
+        let marker = "<!--- benchmarking table --->";
+        let mut s = format!("foo\n{marker}\nold table\n{marker}\nbaz");
+        update_content(
+            &mut s,
+            &get_mock_timings(),
+            190.0,
+            &TableOptions::default(),
+            &manifest(),
+        )
+        .unwrap();
+        assert!(!s.contains(marker));
+        assert!(!s.contains("old table"));
+        assert!(s.starts_with("foo\n<!-- aoc:benchmarks:start -->\n## Benchmarks\n"));
+        assert!(s.ends_with("**Total: 190.00ms**\n<!-- aoc:benchmarks:end -->\nbaz"));
+    }
+
+    #[test]
+    fn format_benchmarks_with_allocations() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_allocs = Some(AllocStats {
+            allocations: 12,
+            bytes: 4096,
+            peak: 800,
+        });
+
+        let mut s = format!("{}\n{}", START, END);
+        update_content(
+            &mut s,
+            &timings,
+            190.0,
+            &TableOptions::default(),
+            &manifest(),
+        )
+        .unwrap();
+
+        let lines: Vec<&str> = s.lines().collect();
+        assert_eq!(
+            lines[3],
+            "| Day | Part 1 | Part 2 | Part 1 heap | Part 2 heap | Share | Cost |"
+        );
+        assert_eq!(
+            lines[5],
+            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` | `12` allocs, `4.0 KiB`, peak `800 B` | - | 15.8% | `██████▋` |"
+        );
+        assert_eq!(
+            lines[6],
+            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` | - | - | 36.8% | `███████████████▌` |"
+        );
+    }
+
+    #[test]
+    fn format_benchmarks_with_parse_step() {
+        let mut timings = get_mock_timings();
+        timings.data[1].parse = Some("5ms".into());
+
+        let mut s = format!("{}\n{}", START, END);
+        update_content(
+            &mut s,
+            &timings,
+            190.0,
+            &TableOptions::default(),
+            &manifest(),
+        )
+        .unwrap();
+
+        let lines: Vec<&str> = s.lines().collect();
+        assert_eq!(lines[3], "| Day | Parse | Part 1 | Part 2 | Share | Cost |");
+        assert_eq!(
+            lines[4],
+            "| :---: | :---: | :---: | :---: | :---: | :---: |"
+        );
+        assert_eq!(
+            lines[5],
+            "| [Day 1](./src/bin/01.rs) | `-` | `10ms` | `20ms` | 15.8% | `██████▋` |"
+        );
+        assert_eq!(
+            lines[6],
+            "| [Day 2](./src/bin/02.rs) | `5ms` | `30ms` | `40ms` | 36.8% | `███████████████▌` |"
+        );
+    }
 }
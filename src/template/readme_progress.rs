@@ -0,0 +1,236 @@
+/// Module that updates the readme with an overview of all days: puzzle titles, stars, solutions, examples and tests.
+use std::fs;
+
+use crate::template::aoc_cli::{get_puzzle_path, get_year};
+use crate::template::manifest::{DayEntry, Manifest};
+use crate::template::readme_sections::{replace_section, Error, ManagedReadme, Section};
+use crate::template::{all_days, Day};
+
+pub const SECTION: Section = Section {
+    name: "progress",
+    legacy_marker: None,
+};
+
+/// State of a single day, as shown in the progress table.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DayProgress {
+    pub day: Day,
+    /// Read from the puzzle description downloaded by `cargo download`.
+    pub title: Option<String>,
+    /// Number of parts with a recorded answer.
+    pub stars: u8,
+    /// Link to the solution binary, if the day has been scaffolded.
+    pub solution: Option<String>,
+    pub has_examples: bool,
+    pub has_tests: bool,
+}
+
+impl DayProgress {
+    /// Collects the progress of a day from the project manifest and the downloaded puzzle description.
+    pub fn read(day: Day, manifest: &Manifest) -> Self {
+        let entry = manifest.get(day);
+        let source = entry.and_then(|entry| fs::read_to_string(&entry.bin).ok());
+
+        let title = fs::read_to_string(get_puzzle_path(day))
+            .ok()
+            .and_then(|puzzle| parse_title(&puzzle, day));
+
+        #[allow(clippy::cast_possible_truncation)]
+        let stars = entry.map_or(0, |entry| {
+            [1, 2]
+                .iter()
+                .filter(|&&p| entry.answer.part(p).is_some())
+                .count() as u8
+        });
+
+        // `cargo scaffold` registers an empty example file, which does not count until it is filled in.
+        let has_examples = entry.is_some_and(|entry| {
+            entry
+                .examples
+                .iter()
+                .any(|path| fs::read_to_string(path).is_ok_and(|s| !s.trim().is_empty()))
+        });
+
+        DayProgress {
+            day,
+            title,
+            stars,
+            solution: entry.map(DayEntry::link),
+            has_examples,
+            has_tests: source.is_some_and(|s| has_tests(&s)),
+        }
+    }
+}
+
+/// Extracts the puzzle title from a heading such as `## \-\-\- Day 1: Calorie Counting \-\-\-`.
+fn parse_title(puzzle: &str, day: Day) -> Option<String> {
+    let prefix = format!("Day {}: ", day.into_inner());
+
+    puzzle.lines().find_map(|line| {
+        let (_, title) = line.split_once(&prefix)?;
+        let title = title.trim_end_matches(['-', '\\', ' ']);
+        (!title.is_empty()).then(|| title.to_string())
+    })
+}
+
+fn has_tests(source: &str) -> bool {
+    source
+        .lines()
+        .any(|l| matches!(l.trim(), "#[test]" | "#[rstest]"))
+}
+
+fn construct_table(prefix: &str, progress: &[DayProgress], year: Option<u16>) -> String {
+    let mark = |b: bool| if b { "✔" } else { "✖" };
+
+    let mut lines: Vec<String> = vec![
+        format!("{prefix} Progress"),
+        String::new(),
+        "| Day | Puzzle | Stars | Solution | Examples | Tests |".into(),
+        "| :---: | :--- | :---: | :---: | :---: | :---: |".into(),
+    ];
+
+    for p in progress {
+        let n = p.day.into_inner();
+        let title = p.title.clone().unwrap_or_else(|| format!("Day {n}"));
+        let puzzle = match year {
+            Some(year) => format!("[{title}](https://adventofcode.com/{year}/day/{n})"),
+            None => title,
+        };
+        let solution = p
+            .solution
+            .as_ref()
+            .map_or("-".into(), |link| format!("[{}.rs]({link})", p.day));
+
+        lines.push(format!(
+            "| {n} | {puzzle} | {} | {solution} | {} | {} |",
+            "⭐".repeat(p.stars.into()),
+            mark(p.has_examples),
+            mark(p.has_tests)
+        ));
+    }
+
+    let stars: u32 = progress.iter().map(|p| u32::from(p.stars)).sum();
+    lines.push(String::new());
+    lines.push(format!("**Stars: {stars}/{}**", progress.len() * 2));
+
+    lines.join("\n")
+}
+
+fn update_content(
+    s: &mut String,
+    progress: &[DayProgress],
+    year: Option<u16>,
+) -> Result<(), Error> {
+    let table = construct_table("##", progress, year);
+    replace_section(s, &SECTION, &table)
+}
+
+/// Puts the progress table into the readme, without writing it.
+pub fn update_section(readme: &mut ManagedReadme) -> Result<(), Error> {
+    let manifest = Manifest::read_from_file().map_err(Error::Parser)?;
+    let progress: Vec<_> = all_days()
+        .map(|day| DayProgress::read(day, &manifest))
+        .collect();
+
+    update_content(readme.content_mut(), &progress, get_year())
+}
+
+pub fn update() -> Result<(), Error> {
+    let mut readme = ManagedReadme::read()?;
+    update_section(&mut readme)?;
+    readme.write()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{has_tests, parse_title, update_content, DayProgress};
+    use crate::day;
+
+    const START: &str = "<!-- aoc:progress:start -->";
+    const END: &str = "<!-- aoc:progress:end -->";
+
+    fn get_mock_progress() -> Vec<DayProgress> {
+        vec![
+            DayProgress {
+                day: day!(1),
+                title: Some("Calorie Counting".into()),
+                stars: 2,
+                solution: Some("./src/bin/01.rs".into()),
+                has_examples: true,
+                has_tests: true,
+            },
+            DayProgress {
+                day: day!(2),
+                title: None,
+                stars: 0,
+                solution: None,
+                has_examples: false,
+                has_tests: false,
+            },
+        ]
+    }
+
+    #[test]
+    fn parses_titles() {
+        let puzzle =
+            "\n## \\-\\-\\- Day 7: No Space Left On Device \\-\\-\\-\n\nYou can hear birds";
+        assert_eq!(
+            parse_title(puzzle, day!(7)),
+            Some("No Space Left On Device".into())
+        );
+        assert_eq!(
+            parse_title("## --- Day 1: Calorie Counting ---", day!(1)),
+            Some("Calorie Counting".into())
+        );
+        assert_eq!(
+            parse_title("## --- Day 1: Calorie Counting ---", day!(2)),
+            None
+        );
+    }
+
+    #[test]
+    fn detects_tests() {
+        assert!(has_tests("mod tests {\n    #[test]\n    fn a() {}\n}"));
+        assert!(has_tests("    #[rstest]\n    #[case(1)]\n"));
+        assert!(!has_tests("fn main() {}"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_marker_not_present() {
+        let mut s = "# readme".to_string();
+        update_content(&mut s, &get_mock_progress(), Some(2022)).unwrap();
+    }
+
+    #[test]
+    fn format_progress() {
+        let mut s = format!("foo\n{}\n{}\nbaz", START, END);
+        update_content(&mut s, &get_mock_progress(), Some(2022)).unwrap();
+        let expected = [
+            "foo",
+            "<!-- aoc:progress:start -->",
+            "## Progress",
+            "",
+            "| Day | Puzzle | Stars | Solution | Examples | Tests |",
+            "| :---: | :--- | :---: | :---: | :---: | :---: |",
+            "| 1 | [Calorie Counting](https://adventofcode.com/2022/day/1) | ⭐⭐ | [01.rs](./src/bin/01.rs) | ✔ | ✔ |",
+            "| 2 | [Day 2](https://adventofcode.com/2022/day/2) |  | - | ✖ | ✖ |",
+            "",
+            "**Stars: 2/4**",
+            "<!-- aoc:progress:end -->",
+            "baz",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn updates_existing_progress() {
+        let mut s = format!("{}\n{}", START, END);
+        update_content(&mut s, &get_mock_progress(), None).unwrap();
+        update_content(&mut s, &get_mock_progress(), None).unwrap();
+        assert_eq!(s.matches(START).count(), 1);
+        assert_eq!(s.matches("## Progress").count(), 1);
+        assert!(s.contains("| 1 | Calorie Counting | ⭐⭐ |"));
+    }
+}
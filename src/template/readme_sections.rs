@@ -0,0 +1,369 @@
+/// Module that keeps the generated sections of the readme up to date.
+/// A section is enclosed by named markers, e.g. `<!-- aoc:benchmarks:start -->` and `<!-- aoc:benchmarks:end -->`.
+/// Everything between them belongs to the generator and is replaced on update, the rest of the readme is left alone.
+use std::{fs, io, ops::Range};
+
+const README_PATH: &str = "README.md";
+
+/// Lines of unchanged context around each change in a diff.
+const DIFF_CONTEXT: usize = 2;
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(msg) => write!(f, "{msg}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// A generated section of the readme.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Section {
+    pub name: &'static str,
+    /// Marker that enclosed the section before named markers existed.
+    /// A block between a pair of these is converted to named markers on update.
+    pub legacy_marker: Option<&'static str>,
+}
+
+impl Section {
+    #[must_use]
+    pub fn start_marker(&self) -> String {
+        format!("<!-- aoc:{}:start -->", self.name)
+    }
+
+    #[must_use]
+    pub fn end_marker(&self) -> String {
+        format!("<!-- aoc:{}:end -->", self.name)
+    }
+}
+
+/// Byte offsets of the lines that consist of the marker only.
+/// Markers quoted in the text, e.g. in documentation of the readme itself, are not matched.
+fn find_marker(readme: &str, marker: &str) -> Vec<usize> {
+    let mut offset = 0;
+    let mut matches = Vec::new();
+
+    for line in readme.split_inclusive('\n') {
+        if line.trim() == marker {
+            matches.push(offset + line.len() - line.trim_start().len());
+        }
+        offset += line.len();
+    }
+
+    matches
+}
+
+/// Finds the byte range of a section, including its markers.
+fn locate(readme: &str, section: &Section) -> Result<Range<usize>, Error> {
+    let start_marker = section.start_marker();
+    let end_marker = section.end_marker();
+
+    let starts = find_marker(readme, &start_marker);
+    let ends = find_marker(readme, &end_marker);
+
+    match (starts.as_slice(), ends.as_slice()) {
+        ([], []) => match section.legacy_marker {
+            Some(legacy) if !find_marker(readme, legacy).is_empty() => {
+                locate_legacy(readme, legacy)
+            }
+            _ => Err(Error::Parser(format!(
+                "Could not find the {} section, add `{start_marker}` and `{end_marker}` to the README.",
+                section.name
+            ))),
+        },
+        ([start], [end]) if end < start => Err(Error::Parser(format!(
+            "{end_marker}: end marker comes before the start marker in README."
+        ))),
+        ([start], [end]) => Ok(*start..end + end_marker.len()),
+        ([], _) => Err(Error::Parser(format!(
+            "{start_marker}: could not find start marker in README."
+        ))),
+        (_, []) => Err(Error::Parser(format!(
+            "{end_marker}: could not find end marker in README."
+        ))),
+        (starts, _) if starts.len() > 1 => Err(Error::Parser(format!(
+            "{start_marker}: too many occurrences of marker in README."
+        ))),
+        _ => Err(Error::Parser(format!(
+            "{end_marker}: too many occurrences of marker in README."
+        ))),
+    }
+}
+
+fn locate_legacy(readme: &str, marker: &str) -> Result<Range<usize>, Error> {
+    let matches = find_marker(readme, marker);
+
+    match matches.as_slice() {
+        [start, end] => Ok(*start..end + marker.len()),
+        _ => Err(Error::Parser(format!(
+            "{marker}: expected a pair of markers in README, found {}.",
+            matches.len()
+        ))),
+    }
+}
+
+/// Replaces the contents of a section in place.
+pub fn replace_section(readme: &mut String, section: &Section, body: &str) -> Result<(), Error> {
+    let range = locate(readme, section)?;
+    let block = format!(
+        "{}\n{body}\n{}",
+        section.start_marker(),
+        section.end_marker()
+    );
+    readme.replace_range(range, &block);
+    Ok(())
+}
+
+/// The readme with pending section updates, which can be previewed as a diff before it is written.
+pub struct ManagedReadme {
+    original: String,
+    content: String,
+}
+
+impl ManagedReadme {
+    #[must_use]
+    pub fn new(content: String) -> Self {
+        ManagedReadme {
+            original: content.clone(),
+            content,
+        }
+    }
+
+    pub fn read() -> Result<Self, Error> {
+        let content = String::from_utf8_lossy(&fs::read(README_PATH)?).to_string();
+        Ok(ManagedReadme::new(content))
+    }
+
+    /// Content with all updates applied so far, for generators that replace sections themselves.
+    pub fn content_mut(&mut self) -> &mut String {
+        &mut self.content
+    }
+
+    #[must_use]
+    pub fn is_changed(&self) -> bool {
+        self.original != self.content
+    }
+
+    /// Line diff between the readme on disk and the updated readme.
+    #[must_use]
+    pub fn diff(&self) -> String {
+        diff_lines(&self.original, &self.content)
+    }
+
+    /// Writes the readme, if any section changed.
+    pub fn write(&self) -> Result<(), Error> {
+        if self.is_changed() {
+            fs::write(README_PATH, &self.content)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Change {
+    Keep,
+    Remove,
+    Add,
+}
+
+/// Renders a unified-style diff with a few lines of context around each change.
+fn diff_lines(old: &str, new: &str) -> String {
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+
+    // lengths of the longest common subsequence of `a[i..]` and `b[j..]`.
+    let mut lcs = vec![vec![0_usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    // each entry holds the change, the line and its 1-based line numbers in the old and new text.
+    let mut ops: Vec<(Change, &str, usize, usize)> = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            ops.push((Change::Keep, a[i], i + 1, j + 1));
+            i += 1;
+            j += 1;
+        } else if i < a.len() && (j == b.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            ops.push((Change::Remove, a[i], i + 1, j + 1));
+            i += 1;
+        } else {
+            ops.push((Change::Add, b[j], i + 1, j + 1));
+            j += 1;
+        }
+    }
+
+    let changed: Vec<usize> = (0..ops.len())
+        .filter(|&k| ops[k].0 != Change::Keep)
+        .collect();
+
+    let is_shown = |k: usize| changed.iter().any(|&c| c.abs_diff(k) <= DIFF_CONTEXT);
+
+    let mut lines = Vec::new();
+    let mut previous_shown = false;
+    for (k, &(change, line, old_no, new_no)) in ops.iter().enumerate() {
+        if !is_shown(k) {
+            previous_shown = false;
+            continue;
+        }
+        if !previous_shown {
+            lines.push(format!("@@ -{old_no} +{new_no} @@"));
+        }
+        previous_shown = true;
+
+        let sign = match change {
+            Change::Keep => ' ',
+            Change::Remove => '-',
+            Change::Add => '+',
+        };
+        lines.push(format!("{sign}{line}"));
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{diff_lines, replace_section, ManagedReadme};
+    use crate::template::{
+        readme_benchmarks::SECTION as BENCHMARKS, readme_progress::SECTION as PROGRESS,
+    };
+
+    #[test]
+    fn formats_markers() {
+        assert_eq!(BENCHMARKS.start_marker(), "<!-- aoc:benchmarks:start -->");
+        assert_eq!(BENCHMARKS.end_marker(), "<!-- aoc:benchmarks:end -->");
+    }
+
+    #[test]
+    fn replaces_sections_independently() {
+        let mut s = [
+            "# readme",
+            "<!-- aoc:progress:start -->",
+            "<!-- aoc:progress:end -->",
+            "text",
+            "<!-- aoc:benchmarks:start -->",
+            "old table",
+            "<!-- aoc:benchmarks:end -->",
+            "footer",
+        ]
+        .join("\n");
+
+        replace_section(&mut s, &BENCHMARKS, "new table").unwrap();
+        replace_section(&mut s, &PROGRESS, "stars").unwrap();
+        replace_section(&mut s, &PROGRESS, "more stars").unwrap();
+
+        let expected = [
+            "# readme",
+            "<!-- aoc:progress:start -->",
+            "more stars",
+            "<!-- aoc:progress:end -->",
+            "text",
+            "<!-- aoc:benchmarks:start -->",
+            "new table",
+            "<!-- aoc:benchmarks:end -->",
+            "footer",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn migrates_legacy_markers() {
+        let marker = "<!--- benchmarking table --->";
+        let mut s = format!("foo\n{marker}\nold table\n{marker}\nbar");
+        replace_section(&mut s, &BENCHMARKS, "new table").unwrap();
+        assert_eq!(
+            s,
+            "foo\n<!-- aoc:benchmarks:start -->\nnew table\n<!-- aoc:benchmarks:end -->\nbar"
+        );
+    }
+
+    #[test]
+    fn reports_invalid_markers() {
+        let error = |s: &str| {
+            replace_section(&mut s.to_string(), &BENCHMARKS, "")
+                .unwrap_err()
+                .to_string()
+        };
+
+        assert!(error("# readme").contains("Could not find the benchmarks section"));
+        assert!(error("<!-- aoc:benchmarks:start -->").contains("could not find end marker"));
+        assert!(
+            error("<!-- aoc:benchmarks:end -->\n<!-- aoc:benchmarks:start -->")
+                .contains("end marker comes before the start marker")
+        );
+        assert_eq!(
+            error("<!-- aoc:benchmarks:start -->\n<!-- aoc:benchmarks:start -->\n<!-- aoc:benchmarks:end -->"),
+            "<!-- aoc:benchmarks:start -->: too many occurrences of marker in README."
+        );
+        assert!(error("<!--- benchmarking table --->").contains("found 1"));
+    }
+
+    #[test]
+    fn ignores_quoted_markers() {
+        let mut s = [
+            "Keep `<!-- aoc:benchmarks:start -->` on its own line.",
+            "  <!-- aoc:benchmarks:start -->",
+            "<!-- aoc:benchmarks:end -->",
+        ]
+        .join("\n");
+        replace_section(&mut s, &BENCHMARKS, "table").unwrap();
+        assert_eq!(
+            s,
+            "Keep `<!-- aoc:benchmarks:start -->` on its own line.\n  <!-- aoc:benchmarks:start -->\ntable\n<!-- aoc:benchmarks:end -->"
+        );
+    }
+
+    #[test]
+    fn tracks_changes() {
+        let mut readme = ManagedReadme::new(
+            "<!-- aoc:progress:start -->\nstars\n<!-- aoc:progress:end -->".into(),
+        );
+        replace_section(readme.content_mut(), &PROGRESS, "stars").unwrap();
+        assert!(!readme.is_changed());
+        assert_eq!(readme.diff(), "");
+
+        replace_section(readme.content_mut(), &PROGRESS, "more stars").unwrap();
+        assert!(readme.is_changed());
+        assert_eq!(
+            readme.diff(),
+            "@@ -1 +1 @@\n <!-- aoc:progress:start -->\n-stars\n+more stars\n <!-- aoc:progress:end -->"
+        );
+    }
+
+    #[test]
+    fn diffs_with_context() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh";
+        let new = "a\nb\nc\nd\nE\nf\ng\nh";
+        assert_eq!(
+            diff_lines(old, new),
+            ["@@ -3 +3 @@", " c", " d", "-e", "+E", " f", " g"].join("\n")
+        );
+
+        let diff = diff_lines("1\nx\n3\n4\n5\n6\n7\n8\ny", "1\n3\n4\n5\n6\n7\n8\ny\nz");
+        assert_eq!(diff.matches("@@").count(), 4);
+        assert!(diff.contains("-x"));
+        assert!(diff.contains("+z"));
+    }
+}
@@ -1,48 +1,95 @@
-use std::{collections::HashSet, io};
+use std::io;
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{DaySet, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
-    all_days,
+    answers::{Answer, Answers},
+    manifest::Manifest,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// Controls how solution binaries are built and invoked.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RunOptions {
+    pub is_release: bool,
+    /// Benches each part, mirroring the `--time` flag.
+    pub is_timed: bool,
+    /// Builds with the `count-allocs` feature.
+    pub count_allocs: bool,
+    /// Prints solution output to stderr, which keeps stdout free for machine-readable output.
+    pub progress_to_stderr: bool,
+}
+
+impl RunOptions {
+    /// Prints a progress line to stdout, or to stderr if `progress_to_stderr` is set.
+    pub fn print(&self, line: &str) {
+        if self.progress_to_stderr {
+            eprintln!("{line}");
+        } else {
+            println!("{line}");
+        }
+    }
+}
+
+/// Narrows a set of days down to the ones that carry a tag in the project manifest.
+pub fn filter_by_tag(days: DaySet, tag: &str, manifest: &Manifest) -> Result<DaySet, String> {
+    let tagged = manifest.tagged(tag);
+    if tagged.is_empty() {
+        return Err(format!(
+            "no day is tagged \"{tag}\". Tags in use: {}.",
+            manifest.tags().join(", ")
+        ));
+    }
+    Ok(days & tagged)
+}
+
+/// Runs the solutions for a set of days, returning their timings (if timed) and the answers they printed.
+/// Days that are not in the manifest have not been scaffolded yet and count as not solved.
+pub fn run_multi(
+    days_to_run: &DaySet,
+    manifest: &Manifest,
+    options: &RunOptions,
+) -> (Option<Timings>, Answers) {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut answers: Vec<Answer> = Vec::with_capacity(days_to_run.len());
 
     let mut need_space = false;
 
-    // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+    days_to_run.iter().for_each(|day| {
+        if need_space {
+            options.print("");
+        }
+        need_space = true;
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+        options.print(&format!("{ANSI_BOLD}Day {day}{ANSI_RESET}"));
+        options.print("------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        let output = if manifest.get(day).is_some() {
+            child_commands::run_solution(day, options, None).unwrap()
+        } else {
+            vec![]
+        };
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
-            }
-        });
+        if output.is_empty() {
+            options.print("Not solved.");
+        } else {
+            let val = child_commands::parse_exec_time(&output, day);
+            timings.push(val);
+            answers.push(child_commands::parse_answers(&output, day));
+        }
+    });
 
-    if is_timed {
+    let answers = Answers { data: answers };
+
+    if options.is_timed {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
-        println!(
+        options.print(&format!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
-        );
-        Some(timings)
+        ));
+        (Some(timings), answers)
     } else {
-        None
+        (None, answers)
     }
 }
 
@@ -58,16 +105,20 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => write!(f, "could not read output of child process."),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
 }
 
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{Error, RunOptions};
+    use crate::template::{alloc_counter::AllocStats, answers::Answer, Day, ANSI_BOLD, ANSI_RESET};
     use std::{
         io::{BufRead, BufReader},
         path::Path,
@@ -75,34 +126,44 @@ pub mod child_commands {
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
-        }
-
+    /// Run the solution bin for a given day, optionally against an input file other than `data/inputs`.
+    pub fn run_solution(
+        day: Day,
+        options: &RunOptions,
+        input: Option<&Path>,
+    ) -> Result<Vec<String>, Error> {
         let day_padded = day.to_string();
         let mut args = vec!["run", "--quiet", "--bin", &day_padded];
 
-        if is_release {
+        if options.is_release {
             args.push("--release");
         }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
+        if options.count_allocs {
+            args.push("--features");
+            args.push("count-allocs");
+        }
+
+        if options.is_timed || input.is_some() {
             args.push("--");
+        }
+
+        if options.is_timed {
+            // mirror `--time` flag to child invocations.
             args.push("--time");
         }
 
+        let mut cmd = Command::new("cargo");
+        cmd.args(&args);
+
+        if let Some(input) = input {
+            cmd.arg("--input").arg(input);
+        }
+
         // spawn child command with piped stdout/stderr.
         // forward output to stdout/stderr while grabbing stdout lines.
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+        let mut cmd = cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
 
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
@@ -117,7 +178,7 @@ pub mod child_commands {
 
         for line in stdout.lines() {
             let line = line.unwrap();
-            println!("{line}");
+            options.print(&line);
             output.push(line);
         }
 
@@ -130,9 +191,13 @@ pub mod child_commands {
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
         let mut timings = super::Timing {
             day,
+            parse: None,
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
+            parse_allocs: None,
+            part_1_allocs: None,
+            part_2_allocs: None,
         };
 
         output
@@ -148,13 +213,18 @@ pub mod child_commands {
                 };
 
                 let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
+                Some((part, timing_str, nanos, parse_allocs(l)))
             })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
+            .for_each(|(part, timing_str, nanos, allocs)| {
+                if part.contains("Parse") {
+                    timings.parse = Some(timing_str.into());
+                    timings.parse_allocs = allocs;
+                } else if part.contains("Part 1") {
                     timings.part_1 = Some(timing_str.into());
+                    timings.part_1_allocs = allocs;
                 } else if part.contains("Part 2") {
                     timings.part_2 = Some(timing_str.into());
+                    timings.part_2_allocs = allocs;
                 }
 
                 timings.total_nanos += nanos;
@@ -163,6 +233,57 @@ pub mod child_commands {
         timings
     }
 
+    pub fn parse_answers(output: &[String], day: Day) -> Answer {
+        let mut answer = Answer {
+            day,
+            part_1: None,
+            part_2: None,
+        };
+
+        // intermediate results are overwritten with a carriage return, only keep the final one.
+        let mut lines = output
+            .iter()
+            .map(|l| l.rsplit('\r').next().unwrap_or_default())
+            .peekable();
+
+        while let Some(line) = lines.next() {
+            let Some((part, rest)) = line.split_once(": ") else {
+                continue;
+            };
+
+            let value = if rest.starts_with('▼') {
+                // multi-line results are printed below the part line.
+                let mut result = vec![];
+                while let Some(next) = lines.next_if(|l| !l.starts_with("Part ")) {
+                    result.push(next);
+                }
+                Some(result.join("\n"))
+            } else {
+                rest.strip_prefix(ANSI_BOLD)
+                    .and_then(|r| r.split_once(ANSI_RESET))
+                    .map(|(value, _)| value.to_string())
+            };
+
+            match part {
+                "Part 1" => answer.part_1 = value,
+                "Part 2" => answer.part_2 = value,
+                _ => {}
+            }
+        }
+
+        answer
+    }
+
+    /// Parses the allocation summary the runner appends to timings with the `count-allocs` feature.
+    fn parse_allocs(line: &str) -> Option<AllocStats> {
+        line.rsplit(" samples) [")
+            .next()?
+            .trim_end()
+            .strip_suffix(']')?
+            .parse()
+            .ok()
+    }
+
     fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
         s.split(postfix).next()?.parse().ok()
     }
@@ -173,19 +294,22 @@ pub mod child_commands {
             .split(" samples)")
             .next()?
             .split('(')
-            .last()?
+            .next_back()?
             .split('@')
             .next()?
             .trim();
 
-        let parsed_timing = match str_timing {
+        Some((str_timing, parse_duration(str_timing)?))
+    }
+
+    /// Parses a formatted duration (e.g. `74.13µs`) to nanoseconds.
+    pub fn parse_duration(s: &str) -> Option<f64> {
+        match s {
             s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
             s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
             s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
             s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+        }
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -204,7 +328,7 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_answers, parse_exec_time};
 
         use crate::day;
 
@@ -221,6 +345,42 @@ pub mod child_commands {
             assert_approx_eq!(res.total_nanos, 74130074.13_f64);
             assert_eq!(res.part_1.unwrap(), "74.13ns");
             assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.part_1_allocs, None);
+        }
+
+        #[test]
+        fn parses_parse_step() {
+            let res = parse_exec_time(
+                &[
+                    "Parse: ✔ > \x1b[3mbenching\x1b[0m\rParse: ✔ (2ms @ 500 samples)".into(),
+                    "Part 1: 0 (1ms @ 1000 samples)".into(),
+                    "Part 2: 10 (1ms @ 1000 samples)".into(),
+                ],
+                day!(7),
+            );
+            assert_approx_eq!(res.total_nanos, 4000000_f64);
+            assert_eq!(res.parse.unwrap(), "2ms");
+            assert_eq!(res.part_1.unwrap(), "1ms");
+            assert_eq!(res.part_2.unwrap(), "1ms");
+        }
+
+        #[test]
+        fn parses_allocations() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (74.13ns @ 100000 samples) [2 allocs, 48 B, peak 32 B]".into(),
+                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
+            assert_eq!(res.part_1.unwrap(), "74.13ns");
+            let allocs = res.part_1_allocs.unwrap();
+            assert_eq!(allocs.allocations, 2);
+            assert_eq!(allocs.bytes, 48);
+            assert_eq!(allocs.peak, 32);
+            assert_eq!(res.part_2_allocs, None);
         }
 
         #[test]
@@ -252,5 +412,34 @@ pub mod child_commands {
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn parses_answers() {
+            let res = parse_answers(
+                &[
+                    "Part 1: \x1b[1m42\x1b[0m\rPart 1: \x1b[1m42\x1b[0m (1.2ms)".into(),
+                    "Part 2: ▼ \rPart 2: ▼  (3.4µs)".into(),
+                    "##..".into(),
+                    "..##".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.part_1.unwrap(), "42");
+            assert_eq!(res.part_2.unwrap(), "##..\n..##");
+        }
+
+        #[test]
+        fn parses_missing_answers() {
+            let res = parse_answers(
+                &[
+                    "Part 1: ✖\rPart 1: ✖             ".into(),
+                    "Part 2: \x1b[1mCMZ\x1b[0m\rPart 2: \x1b[1mCMZ\x1b[0m (1.2ms @ 10 samples)"
+                        .into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.part_1, None);
+            assert_eq!(res.part_2.unwrap(), "CMZ");
+        }
     }
 }
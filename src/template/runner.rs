@@ -6,38 +6,68 @@ use std::process::Output;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
+use crate::template::alloc_counter::{self, AllocStats};
 use crate::template::ANSI_BOLD;
 use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
 
 pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
+    let (result, duration, samples, allocs) =
         run_timed(func, input, |result| print_result(result, &part_str, ""));
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let mut suffix = format_duration(&duration, samples);
+    if let Some(allocs) = allocs {
+        suffix.push_str(&format!(" [{allocs}]"));
+    }
+
+    print_result(&result, &part_str, &suffix);
 
     if let Some(result) = result {
         submit_result(result, day, part);
     }
 }
 
+/// Run the `parse` function of a solution and return its output, which both parts then borrow.
+/// Parsing is timed on its own, so part timings only cover solving.
+pub fn run_parse<I: Clone, P>(func: impl Fn(I) -> P, input: I) -> P {
+    let (parsed, duration, samples, allocs) = run_timed(func, input, |_| print!("Parse: ✔"));
+
+    let mut suffix = format_duration(&duration, samples);
+    if let Some(allocs) = allocs {
+        suffix.push_str(&format!(" [{allocs}]"));
+    }
+
+    print!("\r");
+    println!("Parse: ✔{suffix}");
+
+    parsed
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// With the `count-allocs` feature, heap usage of the first execution is returned as well.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Duration, u128, Option<AllocStats>) {
+    let counts_allocs = cfg!(all(feature = "count-allocs", not(feature = "dhat-heap")));
+
     let timer = Instant::now();
-    let result = {
+    let (result, allocs) = {
         let input = input.clone();
 
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
+        if counts_allocs {
+            alloc_counter::reset();
+        }
+        let result = func(input);
+        (result, counts_allocs.then(alloc_counter::stats))
     };
     let base_time = timer.elapsed();
 
@@ -49,7 +79,7 @@ fn run_timed<I: Clone, T>(
         (base_time, 1)
     };
 
-    (result, run.0, run.1)
+    (result, run.0, run.1, allocs)
 }
 
 fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
@@ -58,13 +88,8 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    let bench_iterations =
+        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
     let mut timers: Vec<Duration> = vec![];
 
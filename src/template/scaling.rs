@@ -0,0 +1,200 @@
+/// Module that times a solution over a ladder of generated input sizes and reports how its run time grows.
+use std::{env, fs, num::ParseIntError};
+
+use crate::generators;
+use crate::template::run_multi::{child_commands, RunOptions};
+use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Timings of both parts for one generated input size.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sample {
+    pub size: usize,
+    pub part_1: Option<f64>,
+    pub part_2: Option<f64>,
+}
+
+/// Parses a comma separated list of input sizes, e.g. `10,100,1000`.
+pub fn parse_sizes(s: &str) -> Result<Vec<usize>, ParseIntError> {
+    let mut sizes = s
+        .split(',')
+        .map(|size| size.trim().parse())
+        .collect::<Result<Vec<usize>, _>>()?;
+    sizes.sort_unstable();
+    sizes.dedup();
+    Ok(sizes)
+}
+
+/// Times the release build of a day for each size, against inputs generated with the given seed.
+/// Returns `None` if the day has no input generator.
+pub fn run_ladder(day: Day, sizes: &[usize], seed: u64) -> Option<Vec<Sample>> {
+    let mut samples = Vec::with_capacity(sizes.len());
+
+    for (i, &size) in sizes.iter().enumerate() {
+        let input = generators::generate(day, size, seed)?;
+        let path = env::temp_dir().join(format!("aoc-{day}-{size}-{seed}.txt"));
+        fs::write(&path, input).expect("could not write generated input");
+
+        if i > 0 {
+            println!();
+        }
+        println!("{ANSI_BOLD}Day {day}, size {size}{ANSI_RESET}");
+        println!("------");
+
+        let options = RunOptions {
+            is_release: true,
+            is_timed: true,
+            ..RunOptions::default()
+        };
+        let output = child_commands::run_solution(day, &options, Some(&path)).unwrap();
+        let _ = fs::remove_file(&path);
+
+        if output.is_empty() {
+            println!("Not solved.");
+            return Some(samples);
+        }
+
+        let timing = child_commands::parse_exec_time(&output, day);
+        samples.push(Sample {
+            size,
+            part_1: timing
+                .part_1
+                .as_deref()
+                .and_then(child_commands::parse_duration),
+            part_2: timing
+                .part_2
+                .as_deref()
+                .and_then(child_commands::parse_duration),
+        });
+    }
+
+    Some(samples)
+}
+
+/// Estimates `k` in `t ~ n^k` between two samples.
+pub fn growth_exponent(size_a: usize, time_a: f64, size_b: usize, time_b: f64) -> Option<f64> {
+    if size_a == size_b || time_a <= 0_f64 || time_b <= 0_f64 {
+        return None;
+    }
+    Some((time_b / time_a).ln() / (size_b as f64 / size_a as f64).ln())
+}
+
+fn format_nanos(nanos: f64) -> String {
+    match nanos {
+        n if n < 1_000_f64 => format!("{n:.0}ns"),
+        n if n < 1_000_000_f64 => format!("{:.1}µs", n / 1_000_f64),
+        n if n < 1_000_000_000_f64 => format!("{:.1}ms", n / 1_000_000_f64),
+        n => format!("{:.2}s", n / 1_000_000_000_f64),
+    }
+}
+
+fn format_cell(time: Option<f64>, growth: Option<f64>) -> String {
+    match (time, growth) {
+        (Some(time), Some(growth)) => format!("{} (n^{growth:.2})", format_nanos(time)),
+        (Some(time), None) => format_nanos(time),
+        (None, _) => "-".into(),
+    }
+}
+
+/// Renders the growth curve of both parts. Each row shows the run time and the exponent of its growth
+/// relative to the previous size, followed by an overall estimate between the smallest and largest size.
+pub fn render(samples: &[Sample]) -> String {
+    let part_growth = |part: fn(&Sample) -> Option<f64>, a: &Sample, b: &Sample| {
+        growth_exponent(a.size, part(a)?, b.size, part(b)?)
+    };
+    let part_1 = |s: &Sample| s.part_1;
+    let part_2 = |s: &Sample| s.part_2;
+
+    let mut rows = vec![[
+        "Size".to_string(),
+        "Part 1".to_string(),
+        "Part 2".to_string(),
+    ]];
+
+    for (i, sample) in samples.iter().enumerate() {
+        let previous = i.checked_sub(1).map(|i| &samples[i]);
+        rows.push([
+            sample.size.to_string(),
+            format_cell(
+                sample.part_1,
+                previous.and_then(|p| part_growth(part_1, p, sample)),
+            ),
+            format_cell(
+                sample.part_2,
+                previous.and_then(|p| part_growth(part_2, p, sample)),
+            ),
+        ]);
+    }
+
+    let widths: Vec<usize> = (0..3)
+        .map(|c| rows.iter().map(|r| r[c].chars().count()).max().unwrap_or(0))
+        .collect();
+
+    let mut lines: Vec<String> = rows
+        .iter()
+        .map(|row| {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{cell:<width$}"))
+                .collect();
+            cells.join("  ").trim_end().to_string()
+        })
+        .collect();
+
+    if let (Some(first), Some(last)) = (samples.first(), samples.last()) {
+        let overall = |part: fn(&Sample) -> Option<f64>| {
+            part_growth(part, first, last).map_or("-".into(), |k| format!("n^{k:.2}"))
+        };
+        lines.push(String::new());
+        lines.push(format!(
+            "{ANSI_ITALIC}Overall growth: part 1 ~ {}, part 2 ~ {}{ANSI_RESET}",
+            overall(part_1),
+            overall(part_2)
+        ));
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{growth_exponent, parse_sizes, render, Sample};
+
+    #[test]
+    fn parses_sizes() {
+        assert_eq!(parse_sizes("1000, 10,100,10"), Ok(vec![10, 100, 1000]));
+        assert!(parse_sizes("10,ten").is_err());
+    }
+
+    #[test]
+    fn estimates_growth_exponent() {
+        let linear = growth_exponent(10, 5.0, 100, 50.0).unwrap();
+        assert!((linear - 1.0).abs() < 1e-9);
+        let quadratic = growth_exponent(10, 1.0, 1000, 10_000.0).unwrap();
+        assert!((quadratic - 2.0).abs() < 1e-9);
+        assert_eq!(growth_exponent(10, 1.0, 10, 2.0), None);
+        assert_eq!(growth_exponent(10, 0.0, 100, 2.0), None);
+    }
+
+    #[test]
+    fn renders_growth_table() {
+        let samples = [
+            Sample {
+                size: 10,
+                part_1: Some(1_000.0),
+                part_2: Some(2_000.0),
+            },
+            Sample {
+                size: 100,
+                part_1: Some(10_000.0),
+                part_2: None,
+            },
+        ];
+        let table = render(&samples);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[0], "Size  Part 1           Part 2");
+        assert_eq!(lines[1], "10    1.0µs            2.0µs");
+        assert_eq!(lines[2], "100   10.0µs (n^1.00)  -");
+        assert!(lines[4].contains("part 1 ~ n^1.00, part 2 ~ -"));
+    }
+}
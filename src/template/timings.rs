@@ -1,7 +1,7 @@
 use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{alloc_counter::AllocStats, Day, DaySet};
 
 static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 
@@ -9,9 +9,14 @@ static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
+    /// Only set for solutions that declare a `parse` function.
+    pub parse: Option<String>,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
     pub total_nanos: f64,
+    pub parse_allocs: Option<AllocStats>,
+    pub part_1_allocs: Option<AllocStats>,
+    pub part_2_allocs: Option<AllocStats>,
 }
 
 /// Represents benchmark times for a set of days.
@@ -44,21 +49,26 @@ impl Timings {
         }
     }
 
-    /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// Merge two sets of timings, overwriting `self` with `other` if present. Allocation stats that
+    /// `other` did not measure, e.g. because it was timed without `--allocs`, keep their recorded value.
     pub fn merge(&self, new: &Self) -> Self {
-        let mut data: Vec<Timing> = vec![];
+        let mut data: Vec<Timing> = self.data.clone();
 
         for timing in &new.data {
-            data.push(timing.clone());
-        }
-
-        for timing in &self.data {
-            if !data.iter().any(|t| t.day == timing.day) {
-                data.push(timing.clone());
+            match data.iter_mut().find(|t| t.day == timing.day) {
+                Some(old) => {
+                    *old = Timing {
+                        parse_allocs: timing.parse_allocs.or(old.parse_allocs),
+                        part_1_allocs: timing.part_1_allocs.or(old.part_1_allocs),
+                        part_2_allocs: timing.part_2_allocs.or(old.part_2_allocs),
+                        ..timing.clone()
+                    };
+                }
+                None => data.push(timing.clone()),
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|t| t.day);
         Timings { data }
     }
 
@@ -68,9 +78,16 @@ impl Timings {
     }
 
     pub fn is_day_complete(&self, day: Day) -> bool {
+        self.complete_days().contains(day)
+    }
+
+    /// Days that have timings for both parts.
+    pub fn complete_days(&self) -> DaySet {
         self.data
             .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+            .filter(|t| t.part_1.is_some() && t.part_2.is_some())
+            .map(|t| t.day)
+            .collect()
     }
 }
 
@@ -140,10 +157,65 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        map.insert(
+            "parse".into(),
+            value
+                .parse
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        map.insert("parse_allocs".into(), allocs_to_json(value.parse_allocs));
+        map.insert("part_1_allocs".into(), allocs_to_json(value.part_1_allocs));
+        map.insert("part_2_allocs".into(), allocs_to_json(value.part_2_allocs));
+
         JsonValue::Object(map)
     }
 }
 
+fn allocs_to_json(allocs: Option<AllocStats>) -> JsonValue {
+    let Some(allocs) = allocs else {
+        return JsonValue::Null;
+    };
+
+    let mut map: HashMap<String, JsonValue> = HashMap::new();
+    #[allow(clippy::cast_precision_loss)]
+    {
+        map.insert(
+            "allocations".into(),
+            JsonValue::Number(allocs.allocations as f64),
+        );
+        map.insert("bytes".into(), JsonValue::Number(allocs.bytes as f64));
+        map.insert("peak".into(), JsonValue::Number(allocs.peak as f64));
+    }
+    JsonValue::Object(map)
+}
+
+/// Allocation counts are optional, as they are only recorded with the `count-allocs` feature.
+fn allocs_from_json(value: Option<&JsonValue>) -> Result<Option<AllocStats>, String> {
+    let Some(value) = value.filter(|v| !v.is_null()) else {
+        return Ok(None);
+    };
+
+    let json = value
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or("Expected timing allocations to be null or a JSON object.")?;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let field = |key: &str| {
+        json.get(key)
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64)
+            .ok_or(format!("Expected timing allocations.{key} to be a number."))
+    };
+
+    Ok(Some(AllocStats {
+        allocations: field("allocations")?,
+        bytes: field("bytes")?,
+        peak: field("peak")?,
+    }))
+}
+
 impl TryFrom<&JsonValue> for Timing {
     type Error = String;
 
@@ -168,6 +240,18 @@ impl TryFrom<&JsonValue> for Timing {
             .map(|v| if v.is_null() { None } else { v.get::<String>() })
             .ok_or("Expected timing.part_2 to be null or string.")?;
 
+        // timings stored before parse steps were timed have no `parse` key.
+        let parse = json
+            .get("parse")
+            .map_or(Some(None), |v| {
+                if v.is_null() {
+                    Some(None)
+                } else {
+                    v.get::<String>().map(Some)
+                }
+            })
+            .ok_or("Expected timing.parse to be null or string.")?;
+
         let total_nanos = json
             .get("total_nanos")
             .and_then(|v| v.get::<f64>().copied())
@@ -175,9 +259,13 @@ impl TryFrom<&JsonValue> for Timing {
 
         Ok(Timing {
             day,
+            parse: parse.cloned(),
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
             total_nanos,
+            parse_allocs: allocs_from_json(json.get("parse_allocs"))?,
+            part_1_allocs: allocs_from_json(json.get("part_1_allocs"))?,
+            part_2_allocs: allocs_from_json(json.get("part_2_allocs"))?,
         })
     }
 }
@@ -195,21 +283,33 @@ mod tests {
             data: vec![
                 Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    parse_allocs: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
                 },
                 Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    parse_allocs: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
                 },
                 Timing {
                     day: day!(4),
+                    parse: None,
                     part_1: Some("40ms".into()),
                     part_2: None,
                     total_nanos: 4e+10,
+                    parse_allocs: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
                 },
             ],
         }
@@ -230,6 +330,27 @@ mod tests {
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_json_allocations() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": "2ms", "total_nanos": 3000000, "part_1_allocs": { "allocations": 12, "bytes": 4096, "peak": 800 }, "part_2_allocs": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let allocs = timing.part_1_allocs.unwrap();
+            assert_eq!(allocs.allocations, 12);
+            assert_eq!(allocs.bytes, 4096);
+            assert_eq!(allocs.peak, 800);
+            assert_eq!(timing.part_2_allocs, None);
+        }
+
+        #[test]
+        fn handles_json_parse_timings() {
+            let json = r#"{ "data": [{ "day": "07", "parse": "2ms", "part_1": "1ms", "part_2": "1ms", "total_nanos": 4000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.parse, Some("2ms".to_string()));
+            assert_eq!(timing.parse_allocs, None);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -286,13 +407,17 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    parse_allocs: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -300,13 +425,17 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("1ms".into()),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    parse_allocs: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -314,20 +443,27 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    parse_allocs: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
     mod merge {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::{
+                alloc_counter::AllocStats,
+                timings::{Timing, Timings},
+            },
         };
 
         use super::get_mock_timings;
@@ -338,9 +474,13 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(3),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    parse_allocs: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -358,9 +498,13 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    parse_allocs: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -372,6 +516,29 @@ mod tests {
             assert_eq!(merged.data[2].day, day!(4));
         }
 
+        #[test]
+        fn keeps_alloc_stats_not_measured_again() {
+            let stats = AllocStats {
+                allocations: 3,
+                bytes: 96,
+                peak: 64,
+            };
+            let mut timings = get_mock_timings();
+            timings.data[1].part_1_allocs = Some(stats);
+            timings.data[1].part_2_allocs = Some(stats);
+
+            let mut timing = timings.data[1].clone();
+            timing.total_nanos = 0_f64;
+            timing.part_1_allocs = None;
+            timing.part_2_allocs = Some(AllocStats::default());
+            let merged = timings.merge(&Timings { data: vec![timing] });
+
+            assert_eq!(merged.data[1].total_nanos, 0_f64);
+            assert_eq!(merged.data[1].part_1_allocs, Some(stats));
+            assert_eq!(merged.data[1].part_2_allocs, Some(AllocStats::default()));
+            assert_eq!(merged.data[1].parse_allocs, None);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let timings = Timings::default();
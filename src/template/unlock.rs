@@ -0,0 +1,235 @@
+/// Module that waits for a puzzle to unlock, used by `cargo today --wait`.
+/// Time is read through a [`Clock`], so the waiting logic can be tested without sleeping.
+use std::{fmt::Display, time::Duration};
+
+use chrono::{DateTime, Datelike, Utc};
+
+use crate::template::{day::server_offset, Day};
+
+/// Longest sleep between two countdown updates.
+const TICK: Duration = Duration::from_secs(1);
+
+/// Delay before the first retry, doubled after every failed attempt.
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+pub trait Clock {
+    fn now(&self) -> DateTime<Utc>;
+    fn sleep(&self, duration: Duration);
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        std::thread::sleep(duration);
+    }
+}
+
+/// Returns the puzzle to wait for: today's puzzle if it is already unlocked, otherwise the next one.
+/// Between the 26th of december and the next advent, that is the 1st of december of the following year.
+pub fn next_unlock(now: DateTime<Utc>) -> (Day, DateTime<Utc>) {
+    let first = Day::new(1).expect("1 to be a valid day");
+    let year = now.with_timezone(&server_offset()).year();
+
+    match Day::today_at(now) {
+        Some(day) => (day, day.unlock_instant(year)),
+        None if now < first.unlock_instant(year) => (first, first.unlock_instant(year)),
+        None => (first, first.unlock_instant(year + 1)),
+    }
+}
+
+/// Sleeps until the given instant. `on_tick` is called with the remaining time before every sleep.
+pub fn wait_until(clock: &impl Clock, instant: DateTime<Utc>, mut on_tick: impl FnMut(Duration)) {
+    loop {
+        let Ok(remaining) = (instant - clock.now()).to_std() else {
+            return;
+        };
+        if remaining.is_zero() {
+            return;
+        }
+        on_tick(remaining);
+        clock.sleep(remaining.min(TICK));
+    }
+}
+
+/// Calls `op` until it succeeds or `attempts` calls have failed, doubling the delay between calls.
+pub fn retry_with_backoff<T, E: Display>(
+    clock: &impl Clock,
+    attempts: u32,
+    mut op: impl FnMut() -> Result<T, E>,
+) -> Result<T, E> {
+    let mut delay = INITIAL_BACKOFF;
+    let mut attempt = 1;
+
+    loop {
+        match op() {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt >= attempts => return Err(e),
+            Err(e) => {
+                eprintln!("Attempt {attempt} of {attempts} failed: {e} Retrying in {delay:?}.");
+                clock.sleep(delay);
+                delay *= 2;
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/// Formats the time left until an unlock, e.g. `02:03:04` or `3d 02:03:04`.
+pub fn format_countdown(remaining: Duration) -> String {
+    // round up, so the countdown reaches zero when the puzzle unlocks.
+    let secs = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+    let (days, hours, minutes, seconds) = (
+        secs / 86_400,
+        secs % 86_400 / 3600,
+        secs % 3600 / 60,
+        secs % 60,
+    );
+
+    match days {
+        0 => format!("{hours:02}:{minutes:02}:{seconds:02}"),
+        days => format!("{days}d {hours:02}:{minutes:02}:{seconds:02}"),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        cell::{Cell, RefCell},
+        time::Duration,
+    };
+
+    use chrono::{DateTime, TimeZone, Utc};
+
+    use super::{format_countdown, next_unlock, retry_with_backoff, wait_until, Clock};
+    use crate::day;
+    use crate::template::Day;
+
+    /// A clock that only advances when slept.
+    struct FakeClock {
+        now: Cell<DateTime<Utc>>,
+        sleeps: RefCell<Vec<Duration>>,
+    }
+
+    impl FakeClock {
+        fn at(now: DateTime<Utc>) -> Self {
+            FakeClock {
+                now: Cell::new(now),
+                sleeps: RefCell::new(Vec::new()),
+            }
+        }
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> DateTime<Utc> {
+            self.now.get()
+        }
+
+        fn sleep(&self, duration: Duration) {
+            self.now
+                .set(self.now.get() + chrono::Duration::from_std(duration).unwrap());
+            self.sleeps.borrow_mut().push(duration);
+        }
+    }
+
+    fn utc(year: i32, month: u32, day: u32, hour: u32, min: u32, sec: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, min, sec)
+            .unwrap()
+    }
+
+    #[test]
+    fn computes_day_in_server_timezone() {
+        assert_eq!(Day::today_at(utc(2024, 12, 1, 4, 59, 59)), None);
+        assert_eq!(Day::today_at(utc(2024, 12, 1, 5, 0, 0)), Some(day!(1)));
+        assert_eq!(Day::today_at(utc(2024, 12, 26, 4, 59, 59)), Some(day!(25)));
+        assert_eq!(Day::today_at(utc(2024, 12, 26, 5, 0, 0)), None);
+        assert_eq!(day!(7).unlock_instant(2024), utc(2024, 12, 7, 5, 0, 0));
+    }
+
+    #[test]
+    fn finds_next_unlock() {
+        assert_eq!(
+            next_unlock(utc(2024, 11, 30, 23, 0, 0)),
+            (day!(1), utc(2024, 12, 1, 5, 0, 0))
+        );
+        // still the 9th at the server, so its puzzle is unlocked already.
+        assert_eq!(
+            next_unlock(utc(2024, 12, 10, 4, 0, 0)),
+            (day!(9), utc(2024, 12, 9, 5, 0, 0))
+        );
+        assert_eq!(
+            next_unlock(utc(2024, 12, 28, 12, 0, 0)),
+            (day!(1), utc(2025, 12, 1, 5, 0, 0))
+        );
+        assert_eq!(
+            next_unlock(utc(2025, 1, 2, 0, 0, 0)),
+            (day!(1), utc(2025, 12, 1, 5, 0, 0))
+        );
+    }
+
+    #[test]
+    fn waits_until_unlock() {
+        let unlock = utc(2024, 12, 1, 5, 0, 0);
+        let clock = FakeClock::at(unlock - chrono::Duration::milliseconds(2500));
+
+        let mut ticks = Vec::new();
+        wait_until(&clock, unlock, |remaining| {
+            ticks.push(format_countdown(remaining))
+        });
+
+        assert_eq!(clock.now(), unlock);
+        assert_eq!(
+            *clock.sleeps.borrow(),
+            [
+                Duration::from_secs(1),
+                Duration::from_secs(1),
+                Duration::from_millis(500)
+            ]
+        );
+        assert_eq!(ticks, ["00:00:03", "00:00:02", "00:00:01"]);
+
+        // does not wait for instants in the past.
+        wait_until(&clock, unlock - chrono::Duration::seconds(1), |_| {
+            panic!("should not tick")
+        });
+        assert_eq!(clock.sleeps.borrow().len(), 3);
+    }
+
+    #[test]
+    fn retries_with_backoff() {
+        let clock = FakeClock::at(utc(2024, 12, 1, 5, 0, 0));
+        let mut calls = 0;
+        let result = retry_with_backoff(&clock, 5, || {
+            calls += 1;
+            if calls < 3 {
+                Err("not unlocked yet.")
+            } else {
+                Ok(calls)
+            }
+        });
+        assert_eq!(result, Ok(3));
+        assert_eq!(
+            *clock.sleeps.borrow(),
+            [Duration::from_secs(1), Duration::from_secs(2)]
+        );
+
+        let clock = FakeClock::at(utc(2024, 12, 1, 5, 0, 0));
+        let result: Result<(), _> = retry_with_backoff(&clock, 3, || Err("down."));
+        assert_eq!(result, Err("down."));
+        assert_eq!(clock.sleeps.borrow().len(), 2);
+    }
+
+    #[test]
+    fn formats_countdown() {
+        assert_eq!(format_countdown(Duration::from_millis(200)), "00:00:01");
+        assert_eq!(format_countdown(Duration::from_secs(3723)), "01:02:03");
+        assert_eq!(
+            format_countdown(Duration::from_secs(3 * 86_400 + 59)),
+            "3d 00:00:59"
+        );
+    }
+}
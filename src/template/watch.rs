@@ -0,0 +1,230 @@
+//! Re-runs the tests and the solution of a day whenever one of its files changes.
+//! Changes are detected by polling file modification times, so no file system events are required.
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+const ANSI_CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// Maximum number of compiler error lines shown when a build fails.
+const MAX_ERROR_LINES: usize = 30;
+
+type Snapshot = Vec<(PathBuf, Option<SystemTime>)>;
+
+/// The outcome of a `cargo test` invocation, as parsed from its output.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct TestSummary {
+    pub passed: Vec<String>,
+    pub failed: Vec<String>,
+}
+
+pub fn watch(day: Day, is_release: bool) {
+    let mut last_snapshot: Option<Snapshot> = None;
+
+    loop {
+        let snapshot = take_snapshot(day);
+
+        if last_snapshot.as_ref() != Some(&snapshot) {
+            run_once(day, is_release);
+            // files may have been touched while building, take a fresh snapshot afterwards.
+            last_snapshot = Some(take_snapshot(day));
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Collects all files belonging to a day: its solution and every input or example file, including part suffixes.
+fn watched_paths(day: Day) -> Vec<PathBuf> {
+    let mut paths = vec![PathBuf::from(format!("src/bin/{day}.rs"))];
+
+    for folder in ["inputs", "examples"] {
+        let dir = Path::new("data").join(folder);
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+
+        paths.extend(
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| is_day_file(path, day)),
+        );
+    }
+
+    paths.sort();
+    paths
+}
+
+fn is_day_file(path: &Path, day: Day) -> bool {
+    let day = day.to_string();
+    path.extension().is_some_and(|ext| ext == "txt")
+        && path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .is_some_and(|stem| stem == day || stem.starts_with(&format!("{day}-")))
+}
+
+fn take_snapshot(day: Day) -> Snapshot {
+    watched_paths(day)
+        .into_iter()
+        .map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+            (path, modified)
+        })
+        .collect()
+}
+
+fn run_once(day: Day, is_release: bool) {
+    print!("{ANSI_CLEAR_SCREEN}");
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET} {ANSI_ITALIC}(watching for changes, press Ctrl-C to exit){ANSI_RESET}");
+    println!("------");
+
+    // `--quiet` switches libtest to terse output, force per-test lines so they can be summarized.
+    let tests = match cargo("test", day, is_release, &["--format", "pretty"]) {
+        Ok(output) => output,
+        Err(e) => {
+            eprintln!("Failed to run tests: {e}");
+            return;
+        }
+    };
+
+    let summary = parse_test_output(&String::from_utf8_lossy(&tests.stdout));
+
+    if !tests.status.success() && summary.passed.is_empty() && summary.failed.is_empty() {
+        println!("Build: ✖");
+        print_error_lines(&String::from_utf8_lossy(&tests.stderr));
+        return;
+    }
+
+    print_test_summary(&summary);
+    println!();
+
+    match cargo("run", day, is_release, &[]) {
+        Ok(output) => {
+            for line in String::from_utf8_lossy(&output.stdout).lines() {
+                // intermediate results are overwritten with a carriage return, only keep the final one.
+                println!("{}", line.rsplit('\r').next().unwrap_or_default());
+            }
+            if !output.status.success() {
+                print_error_lines(&String::from_utf8_lossy(&output.stderr));
+            }
+        }
+        Err(e) => eprintln!("Failed to run solution: {e}"),
+    }
+}
+
+fn cargo(
+    command: &str,
+    day: Day,
+    is_release: bool,
+    extra_args: &[&str],
+) -> std::io::Result<Output> {
+    let mut cmd = Command::new("cargo");
+    cmd.args([
+        command,
+        "--quiet",
+        "--color",
+        "never",
+        "--bin",
+        &day.to_string(),
+    ]);
+    if is_release {
+        cmd.arg("--release");
+    }
+    cmd.arg("--").args(extra_args).output()
+}
+
+fn print_test_summary(summary: &TestSummary) {
+    let total = summary.passed.len() + summary.failed.len();
+
+    if summary.failed.is_empty() {
+        println!("Tests: ✔ {total} passed");
+    } else {
+        println!("Tests: ✖ {} of {total} failed", summary.failed.len());
+        for name in &summary.failed {
+            println!("  - {name}");
+        }
+    }
+}
+
+fn print_error_lines(stderr: &str) {
+    let lines: Vec<_> = stderr.lines().filter(|l| !l.trim().is_empty()).collect();
+    for line in lines.iter().take(MAX_ERROR_LINES) {
+        println!("{line}");
+    }
+    if lines.len() > MAX_ERROR_LINES {
+        println!(
+            "{ANSI_ITALIC}... {} more lines{ANSI_RESET}",
+            lines.len() - MAX_ERROR_LINES
+        );
+    }
+}
+
+/// Parses the per-test lines of libtest output, e.g. `test tests::test_part_one ... ok`.
+pub fn parse_test_output(stdout: &str) -> TestSummary {
+    let mut summary = TestSummary::default();
+
+    for line in stdout.lines() {
+        let Some(rest) = line.strip_prefix("test ") else {
+            continue;
+        };
+        let Some((name, result)) = rest.rsplit_once(" ... ") else {
+            continue;
+        };
+
+        match result.trim() {
+            "ok" => summary.passed.push(name.to_string()),
+            "FAILED" => summary.failed.push(name.to_string()),
+            _ => {}
+        }
+    }
+
+    summary
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{is_day_file, parse_test_output};
+    use crate::day;
+    use std::path::Path;
+
+    #[test]
+    fn parses_test_output() {
+        let summary = parse_test_output(
+            [
+                "",
+                "running 3 tests",
+                "test tests::test_part_one ... ok",
+                "test tests::test_part_two ... FAILED",
+                "test tests::start_index_test::case_1 ... ok",
+                "test tests::slow ... ignored",
+                "",
+                "test result: FAILED. 2 passed; 1 failed; 1 ignored; 0 measured; 0 filtered out",
+            ]
+            .join("\n")
+            .as_str(),
+        );
+        assert_eq!(
+            summary.passed,
+            vec!["tests::test_part_one", "tests::start_index_test::case_1"]
+        );
+        assert_eq!(summary.failed, vec!["tests::test_part_two"]);
+    }
+
+    #[test]
+    fn matches_day_files() {
+        assert!(is_day_file(Path::new("data/examples/09.txt"), day!(9)));
+        assert!(is_day_file(Path::new("data/examples/09-2.txt"), day!(9)));
+        assert!(!is_day_file(Path::new("data/examples/19.txt"), day!(9)));
+        assert!(!is_day_file(Path::new("data/examples/09.md"), day!(9)));
+    }
+}
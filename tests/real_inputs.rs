@@ -0,0 +1,81 @@
+//! Regression tests that run every solution against its real input and compare the answers
+//! with the ones recorded in `data/manifest.json` (see `cargo all --record`).
+//!
+//! Days without an input file or without recorded answers are skipped.
+use std::path::Path;
+
+use advent_of_code::template::{answers, answers::Answers, Day};
+
+fn has_input(day: Day) -> bool {
+    let path = format!("data/inputs/{day}.txt");
+    Path::new(&path)
+        .metadata()
+        .is_ok_and(|metadata| metadata.len() > 0)
+}
+
+fn check_day(day: Day) {
+    let Some(expected) = Answers::read_from_file()
+        .expect("a readable project manifest")
+        .get(day)
+        .cloned()
+    else {
+        eprintln!("Day {day}: no recorded answers, skipping.");
+        return;
+    };
+
+    if !has_input(day) {
+        eprintln!("Day {day}: no input file, skipping.");
+        return;
+    }
+
+    let actual = answers::run_day(day, true).expect("solution to produce output");
+
+    for part in [1, 2] {
+        if let Some(expected) = expected.part(part) {
+            assert_eq!(
+                actual.part(part),
+                Some(expected),
+                "day {day}, part {part} does not match the recorded answer"
+            );
+        }
+    }
+}
+
+macro_rules! real_input_tests {
+    ($($name:ident: $day:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                check_day(advent_of_code::day!($day));
+            }
+        )*
+    };
+}
+
+real_input_tests! {
+    day_01: 1,
+    day_02: 2,
+    day_03: 3,
+    day_04: 4,
+    day_05: 5,
+    day_06: 6,
+    day_07: 7,
+    day_08: 8,
+    day_09: 9,
+    day_10: 10,
+    day_11: 11,
+    day_12: 12,
+    day_13: 13,
+    day_14: 14,
+    day_15: 15,
+    day_16: 16,
+    day_17: 17,
+    day_18: 18,
+    day_19: 19,
+    day_20: 20,
+    day_21: 21,
+    day_22: 22,
+    day_23: 23,
+    day_24: 24,
+    day_25: 25,
+}